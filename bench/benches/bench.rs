@@ -1,4 +1,4 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
 
 fn bench_valid_isin(c: &mut Criterion) {
     let isin = b"US5949181045";
@@ -12,6 +12,84 @@ fn bench_valid_isin(c: &mut Criterion) {
     });
 }
 
+fn bench_checksum_isin(c: &mut Criterion) {
+    let body = b"US594918104";
+
+    c.bench_function("checksum isin", |b| {
+        b.iter(|| luhn3::alphanum::checksum(black_box(body)))
+    });
+
+    c.bench_function("checksum isin vec", |b| {
+        b.iter(|| luhn3::alphanum::checksum_vec(black_box(body)))
+    });
+}
+
+/// Reference alphanumeric validation: expand letters into two digits and validate the
+/// result as decimal
+fn naive_alphanum_valid(ascii: &[u8]) -> bool {
+    let mut digits = Vec::with_capacity(ascii.len() * 2);
+    for &c in ascii {
+        match c {
+            b'0'..=b'9' => digits.push(c),
+            b'A'..=b'Z' => {
+                let value = c - b'A' + 10;
+                digits.push(b'0' + value / 10);
+                digits.push(b'0' + value % 10);
+            }
+            _ => return false,
+        }
+    }
+    luhn3::decimal::valid(&digits)
+}
+
+fn bench_alphanum_naive(c: &mut Criterion) {
+    let isins = [
+        &b"US5949181045"[..],
+        b"US38259P5089",
+        b"US0378331005",
+        b"BMG491BT1088",
+        b"IE00B4BNMY34",
+        b"US0231351067",
+        b"US64110L1061",
+        b"US30303M1027",
+        b"CH0031240127",
+        b"CA9861913023",
+    ];
+    for isin in isins {
+        assert_eq!(luhn3::alphanum::valid(isin), naive_alphanum_valid(isin));
+    }
+
+    c.bench_function("validate isins", |b| {
+        b.iter(|| {
+            isins
+                .iter()
+                .filter(|i| luhn3::alphanum::valid(black_box(i)))
+                .count()
+        })
+    });
+
+    c.bench_function("validate isins naive", |b| {
+        b.iter(|| {
+            isins
+                .iter()
+                .filter(|i| naive_alphanum_valid(black_box(i)))
+                .count()
+        })
+    });
+}
+
+fn bench_reject_isin(c: &mut Criterion) {
+    let isin = b"us5949181045";
+
+    c.bench_function("reject isin", |b| {
+        b.iter(|| luhn3::alphanum::valid(black_box(isin)))
+    });
+
+    c.bench_function("reject isin vec", |b| {
+        b.iter(|| luhn3::alphanum::valid_vec(black_box(isin)))
+    });
+}
+
 fn bench_valid_visa(c: &mut Criterion) {
     let visa = b"4111111111111111";
 
@@ -22,7 +100,84 @@ fn bench_valid_visa(c: &mut Criterion) {
     c.bench_function("validate visa arr", |b| {
         b.iter(|| luhn3::decimal::valid_arr(black_box(visa)))
     });
+
+    c.bench_function("validate visa alphanum", |b| {
+        b.iter(|| luhn3::alphanum::valid(black_box(visa)))
+    });
+
+    c.bench_function("validate visa fast", |b| {
+        b.iter(|| luhn3::valid_fast(black_box(visa)))
+    });
+}
+
+fn bench_valid_19_digits(c: &mut Criterion) {
+    let card = b"6205500000000000004";
+
+    c.bench_function("validate 19 digits", |b| {
+        b.iter(|| luhn3::decimal::valid(black_box(card)))
+    });
+
+    c.bench_function("validate 19 digits vec", |b| {
+        b.iter(|| luhn3::decimal::valid_vec(black_box(card)))
+    });
+}
+
+fn bench_valid_lengths(c: &mut Criterion) {
+    let digits = b"4012888888881881".repeat(4);
+    let mut group = c.benchmark_group("validate length");
+    for len in (4..=64).step_by(4) {
+        let input = &digits[..len];
+        group.bench_with_input(BenchmarkId::new("scalar", len), input, |b, input| {
+            b.iter(|| luhn3::decimal::valid(black_box(input)))
+        });
+        group.bench_with_input(BenchmarkId::new("vec", len), input, |b, input| {
+            b.iter(|| luhn3::decimal::valid_vec(black_box(input)))
+        });
+    }
+    group.finish();
+}
+
+/// 16 bytes fit in a single register, a leading zero doesn't change the check digit but
+/// makes the input long enough for the general vectorized fold
+fn bench_short_vs_general(c: &mut Criterion) {
+    let short = b"4012888888881881";
+    let general = b"04012888888881881";
+
+    c.bench_function("validate 16 digits vec", |b| {
+        b.iter(|| luhn3::decimal::valid_vec(black_box(short)))
+    });
+
+    c.bench_function("validate 17 digits vec", |b| {
+        b.iter(|| luhn3::decimal::valid_vec(black_box(general)))
+    });
+}
+
+fn bench_scan(c: &mut Criterion) {
+    let mut text = Vec::with_capacity(1 << 20);
+    while text.len() < 1 << 20 {
+        text.extend_from_slice(b"payment 4012888888881881 accepted at 2024-01-15 12:34:56\n");
+        text.extend_from_slice(b"lorem ipsum dolor sit amet, consectetur adipiscing elit\n");
+    }
+
+    c.bench_function("scan 1M", |b| {
+        b.iter(|| luhn3::decimal::scan(black_box(&text), 16).count())
+    });
+
+    c.bench_function("scan 1M vec", |b| {
+        b.iter(|| luhn3::decimal::scan_vec(black_box(&text), 16).count())
+    });
 }
 
-criterion_group!(benches, bench_valid_isin, bench_valid_visa);
+criterion_group!(
+    benches,
+    bench_valid_isin,
+    bench_checksum_isin,
+    bench_alphanum_naive,
+    bench_reject_isin,
+    bench_valid_visa,
+    bench_valid_19_digits,
+    bench_valid_lengths,
+    bench_short_vs_general,
+    bench_scan
+);
 criterion_main!(benches);
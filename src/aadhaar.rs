@@ -0,0 +1,40 @@
+//! # Aadhaar numbers
+//!
+//! Indian Aadhaar identity number is 12 digits long and ends with a
+//! [Verhoeff][crate::verhoeff] check digit rather than a Luhn one.
+//!
+//! ```
+//! use luhn3::aadhaar;
+//! assert!(aadhaar::valid(b"234123412346"));
+//! assert!(!luhn3::decimal::valid(b"234123412346"));
+//! ```
+use crate::verhoeff;
+
+/// Validate a 12 digit Aadhaar number
+///
+/// ```
+/// use luhn3::aadhaar::valid;
+///
+/// assert!(valid(b"234123412346"));
+///
+/// // wrong check digit
+/// assert!(!valid(b"234123412345"));
+///
+/// // Aadhaar must be exactly 12 digits long
+/// assert!(!valid(b"2363"));
+/// ```
+pub fn valid(id: &[u8]) -> bool {
+    id.len() == 12 && verhoeff::valid(id)
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn test_aadhaar_length() {
+        assert!(super::valid(b"499181234560"));
+        // valid Verhoeff check digit but 13 digits
+        assert!(crate::verhoeff::valid(b"4991812345672"));
+        assert!(!super::valid(b"4991812345672"));
+        assert!(!super::valid(b"49918123456x"));
+    }
+}
@@ -0,0 +1,62 @@
+//! # C interface
+//!
+//! Functions callable from C and other languages with a C FFI, requires `capi` feature.
+//! Crate is built as a Rust library, to get a shared library build it with
+//!
+//! ```text
+//! cargo rustc --release --features capi,std --crate-type cdylib
+//! ```
+//!
+//! `std` provides a panic handler, `no_std` firmware linking the crate statically must
+//! provide its own.
+//!
+//! and declare functions on the C side as
+//!
+//! ```c
+//! bool luhn3_decimal_valid(const uint8_t *ptr, size_t len);
+//! int32_t luhn3_decimal_checksum(const uint8_t *ptr, size_t len);
+//! ```
+use crate::decimal;
+
+/// Borrow a slice passed from C, `None` for a null pointer
+///
+/// # Safety
+/// Unless `ptr` is null it must point to `len` readable bytes
+unsafe fn slice<'a>(ptr: *const u8, len: usize) -> Option<&'a [u8]> {
+    if ptr.is_null() {
+        None
+    } else {
+        // SAFETY: pointer is not null, the rest is up to the caller
+        Some(unsafe { core::slice::from_raw_parts(ptr, len) })
+    }
+}
+
+/// Validate a check digit of `len` ASCII bytes at `ptr`, see [`decimal::valid_vec`]
+///
+/// Returns `false` if `ptr` is null.
+///
+/// # Safety
+/// Unless `ptr` is null it must point to `len` bytes valid for reads that are not modified
+/// for the duration of the call
+#[no_mangle]
+pub unsafe extern "C" fn luhn3_decimal_valid(ptr: *const u8, len: usize) -> bool {
+    // SAFETY: requirements are forwarded to the caller
+    unsafe { slice(ptr, len) }.is_some_and(decimal::valid_vec)
+}
+
+/// Compute a check digit for `len` ASCII bytes at `ptr`, see [`decimal::checksum_vec`]
+///
+/// Returns the check digit as an ASCII byte or `-1` if `ptr` is null or the input is not
+/// decimal.
+///
+/// # Safety
+/// Unless `ptr` is null it must point to `len` bytes valid for reads that are not modified
+/// for the duration of the call
+#[no_mangle]
+pub unsafe extern "C" fn luhn3_decimal_checksum(ptr: *const u8, len: usize) -> i32 {
+    // SAFETY: requirements are forwarded to the caller
+    match unsafe { slice(ptr, len) }.and_then(decimal::checksum_vec) {
+        Some(check) => i32::from(check),
+        None => -1,
+    }
+}
@@ -0,0 +1,274 @@
+//! # Payment card numbers
+//!
+//! [`CardNumber`] keeps track of whether a number already has a check digit in its type so
+//! [`decimal::checksum`] can't be called on a complete number and [`decimal::valid`] can't
+//! be called on a number without a check digit.
+//!
+//! ```
+//! use luhn3::card::CardNumber;
+//!
+//! let card = CardNumber::body(b"401288888888188").unwrap().with_check_digit();
+//! assert_eq!(b"4012888888881881", card.as_bytes());
+//! assert!(card.valid());
+//! ```
+use crate::decimal;
+use core::marker::PhantomData;
+
+/// Maximum number of digits in a card number including the check digit
+pub const MAX_LEN: usize = 19;
+
+/// Minimum number of digits in a card number including the check digit
+pub const MIN_LEN: usize = 12;
+
+/// Card number without a check digit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Unchecked {}
+
+/// Card number ending with a check digit that may or may not be correct
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WithCheckDigit {}
+
+/// Up to [`MAX_LEN`] decimal digits with a state telling if the check digit is present
+///
+/// Number without a check digit can't be validated
+/// ```compile_fail
+///    use luhn3::card::CardNumber;
+///    let card = CardNumber::body(b"401288888888188").unwrap();
+///    card.valid();
+/// ```
+///
+/// And a number with a check digit can't get another one
+/// ```compile_fail
+///    use luhn3::card::CardNumber;
+///    let card = CardNumber::full(b"4012888888881881").unwrap();
+///    card.with_check_digit();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CardNumber<S> {
+    digits: [u8; MAX_LEN],
+    len: usize,
+    state: PhantomData<S>,
+}
+
+impl<S> CardNumber<S> {
+    fn new(digits: &[u8]) -> Option<Self> {
+        if digits.is_empty() || !digits.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        let mut buf = [0; MAX_LEN];
+        buf.get_mut(..digits.len())?.copy_from_slice(digits);
+        Some(Self {
+            digits: buf,
+            len: digits.len(),
+            state: PhantomData,
+        })
+    }
+
+    /// ASCII digits of the number
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.digits[..self.len]
+    }
+}
+
+impl CardNumber<Unchecked> {
+    /// Card number without a check digit, up to `MAX_LEN - 1` digits
+    ///
+    /// Returns `None` if input is empty, too long or contains anything but decimal digits
+    /// ```rust
+    ///    use luhn3::card::CardNumber;
+    ///    assert!(CardNumber::body(b"401288888888188").is_some());
+    ///    assert!(CardNumber::body(b"4012 8888 8888 188").is_none());
+    /// ```
+    pub fn body(digits: &[u8]) -> Option<Self> {
+        if digits.len() >= MAX_LEN {
+            return None;
+        }
+        Self::new(digits)
+    }
+
+    /// Compute a check digit as an ASCII byte, same as [`decimal::checksum`]
+    pub fn checksum(&self) -> u8 {
+        decimal::checksum(self.as_bytes()).expect("card number contains only digits")
+    }
+
+    /// Append a check digit
+    pub fn with_check_digit(self) -> CardNumber<WithCheckDigit> {
+        let check = self.checksum();
+        let mut digits = self.digits;
+        digits[self.len] = check;
+        CardNumber {
+            digits,
+            len: self.len + 1,
+            state: PhantomData,
+        }
+    }
+}
+
+impl CardNumber<WithCheckDigit> {
+    /// Card number ending with a check digit, up to `MAX_LEN` digits
+    ///
+    /// Check digit itself is not validated, use [`CardNumber::valid`] for that. Returns
+    /// `None` if input is empty, too long or contains anything but decimal digits
+    /// ```rust
+    ///    use luhn3::card::CardNumber;
+    ///    assert!(CardNumber::full(b"4012888888881881").unwrap().valid());
+    ///    assert!(!CardNumber::full(b"4012888888881882").unwrap().valid());
+    /// ```
+    pub fn full(digits: &[u8]) -> Option<Self> {
+        Self::new(digits)
+    }
+
+    /// Validate the check digit, same as [`decimal::valid`]
+    pub fn valid(&self) -> bool {
+        decimal::valid(self.as_bytes())
+    }
+
+    /// Last digit of the number as an ASCII byte
+    pub fn check_digit(&self) -> u8 {
+        self.digits[self.len - 1]
+    }
+}
+
+/// Card number split from the front of a buffer by [`parse_bundle`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParsedCard<'a> {
+    /// Card number with a valid check digit
+    pub pan: &'a [u8],
+    /// Everything after the card number
+    pub rest: &'a [u8],
+}
+
+/// Split a card number from the front of a buffer with other fields concatenated to it
+///
+/// Card number must be [`MIN_LEN`] to [`MAX_LEN`] digits long and have a valid check digit.
+/// When several prefixes of the leading digits qualify the longest one is taken, so fields
+/// following the number should start with a separator to avoid ambiguity. Unlike
+/// [`decimal::scan`] the number must be at the very start of the input.
+///
+/// ```
+/// use luhn3::card::parse_bundle;
+///
+/// let card = parse_bundle(b"4111111111111111|12/25").unwrap();
+/// assert_eq!(b"4111111111111111", card.pan);
+/// assert_eq!(b"|12/25", card.rest);
+///
+/// // check digit is wrong
+/// assert_eq!(None, parse_bundle(b"4111111111111112|12/25"));
+/// ```
+pub fn parse_bundle(input: &[u8]) -> Option<ParsedCard<'_>> {
+    let digits = input
+        .iter()
+        .take(MAX_LEN)
+        .take_while(|c| c.is_ascii_digit())
+        .count();
+    let len = (MIN_LEN..=digits)
+        .rev()
+        .find(|&len| decimal::valid(&input[..len]))?;
+    let (pan, rest) = input.split_at(len);
+    Some(ParsedCard { pan, rest })
+}
+
+/// Card number with a valid check digit that is wiped from memory when dropped
+///
+/// Digits are stored inline and overwritten with zeros by [`Zeroize`](zeroize::Zeroize)
+/// on drop. [`Debug`](core::fmt::Debug) shows only the last four digits. Requires
+/// `zeroize` feature.
+///
+/// ```
+/// use luhn3::card::SecretCardNumber;
+///
+/// let card = SecretCardNumber::new(b"4012888888881881").unwrap();
+/// assert_eq!(b"1881", card.last4());
+/// assert_eq!("SecretCardNumber(****1881)", format!("{:?}", card));
+///
+/// assert!(SecretCardNumber::new(b"4012888888881882").is_none());
+/// ```
+#[cfg(feature = "zeroize")]
+pub struct SecretCardNumber {
+    digits: [u8; MAX_LEN],
+    len: usize,
+}
+
+#[cfg(feature = "zeroize")]
+impl SecretCardNumber {
+    /// Store a card number of up to [`MAX_LEN`] digits if its check digit is valid
+    pub fn new(number: &[u8]) -> Option<Self> {
+        if number.len() > MAX_LEN || !decimal::valid(number) {
+            return None;
+        }
+        let mut digits = [0; MAX_LEN];
+        digits[..number.len()].copy_from_slice(number);
+        Some(Self {
+            digits,
+            len: number.len(),
+        })
+    }
+
+    /// ASCII digits of the number
+    pub fn expose(&self) -> &[u8] {
+        &self.digits[..self.len]
+    }
+
+    /// Up to four last digits of the number for masked display
+    pub fn last4(&self) -> &[u8] {
+        &self.digits[self.len.saturating_sub(4)..self.len]
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl core::fmt::Debug for SecretCardNumber {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // only ASCII digits are stored
+        let last4 = core::str::from_utf8(self.last4()).unwrap_or_default();
+        write!(f, "SecretCardNumber(****{})", last4)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for SecretCardNumber {
+    fn zeroize(&mut self) {
+        self.digits.zeroize();
+        self.len.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for SecretCardNumber {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for SecretCardNumber {}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_bundle, ParsedCard};
+
+    #[test]
+    fn test_parse_bundle() {
+        assert_eq!(
+            Some(ParsedCard {
+                pan: b"4111111111111111",
+                rest: b"|12/25"
+            }),
+            parse_bundle(b"4111111111111111|12/25")
+        );
+        assert_eq!(
+            Some(ParsedCard {
+                pan: b"4012888888881888883",
+                rest: b""
+            }),
+            parse_bundle(b"4012888888881888883")
+        );
+        // too short for a card number
+        assert_eq!(None, parse_bundle(b"79927398713|12/25"));
+        assert_eq!(None, parse_bundle(b""));
+        assert_eq!(None, parse_bundle(b"|4111111111111111"));
+        // digits past MAX_LEN are not a part of a card number
+        let card = parse_bundle(b"40128888888818888831225").unwrap();
+        assert_eq!(b"4012888888881888883", card.pan);
+        assert_eq!(b"1225", card.rest);
+    }
+}
@@ -0,0 +1,102 @@
+use core::fmt;
+
+/// Reason why input failed validation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum LuhnError {
+    /// Input is empty so there's no check digit
+    Empty,
+    /// Byte at this index is not allowed in the input
+    InvalidByte(usize),
+    /// Input is well formed but the check digit doesn't match
+    InvalidChecksum,
+    /// Input length doesn't match the expected one
+    InvalidLength,
+}
+
+impl LuhnError {
+    /// Short static description of the error, doesn't need `core::fmt`
+    ///
+    /// Unlike [`Display`](fmt::Display) implementation index of the invalid byte is not
+    /// included
+    /// ```rust
+    ///    use luhn3::LuhnError;
+    ///    assert_eq!("invalid byte", LuhnError::InvalidByte(3).as_str());
+    /// ```
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            LuhnError::Empty => "input is empty",
+            LuhnError::InvalidByte(_) => "invalid byte",
+            LuhnError::InvalidChecksum => "check digit doesn't match",
+            LuhnError::InvalidLength => "input has wrong length",
+        }
+    }
+}
+
+impl fmt::Display for LuhnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LuhnError::InvalidByte(ix) => write!(f, "{} at index {}", self.as_str(), ix),
+            _ => f.write_str(self.as_str()),
+        }
+    }
+}
+
+/// Reason why [`Mixer::push_checked`](crate::Mixer::push_checked) rejected a byte
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum MixerError {
+    /// Byte is not an ASCII decimal digit
+    InvalidByte(u8),
+    /// Mixer already contains the maximum allowed number of digits
+    TooLong,
+}
+
+impl fmt::Display for MixerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MixerError::InvalidByte(b) => write!(f, "invalid byte {:#04x}", b),
+            MixerError::TooLong => f.write_str("input is too long"),
+        }
+    }
+}
+
+/// Report a validation failure if `tracing` or `defmt` feature is enabled
+#[inline(always)]
+pub(crate) fn report(res: Result<(), LuhnError>) -> Result<(), LuhnError> {
+    #[cfg(feature = "tracing")]
+    if let Err(err) = res {
+        tracing::debug!(error = ?err, "luhn validation failed");
+    }
+    #[cfg(feature = "defmt")]
+    if let Err(err) = res {
+        defmt::debug!("luhn validation failed: {}", err);
+    }
+    res
+}
+
+#[cfg(test)]
+mod test {
+    use super::LuhnError;
+
+    #[test]
+    fn test_error_as_str() {
+        let errors = [
+            LuhnError::Empty,
+            LuhnError::InvalidByte(0),
+            LuhnError::InvalidChecksum,
+            LuhnError::InvalidLength,
+        ];
+        for (i, a) in errors.iter().enumerate() {
+            assert!(!a.as_str().is_empty());
+            assert!(a.to_string().starts_with(a.as_str()));
+            for b in &errors[i + 1..] {
+                assert_ne!(a.as_str(), b.as_str());
+            }
+        }
+        assert_eq!(
+            "invalid byte at index 3",
+            LuhnError::InvalidByte(3).to_string()
+        );
+    }
+}
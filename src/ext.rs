@@ -0,0 +1,165 @@
+use crate::{alphanum, decimal};
+
+/// Decimal Luhn checks using method syntax
+///
+/// Implemented for byte slices, strings and byte arrays, results are the same as
+/// [`decimal::valid`] and [`decimal::checksum`].
+///
+/// # Examples
+/// ```rust
+///    use luhn3::Luhn;
+///    assert!("4012888888881881".luhn_valid());
+///    assert!(b"4012888888881881".luhn_valid());
+///    assert_eq!(Some(b'1'), "401288888888188".luhn_checksum());
+/// ```
+pub trait Luhn {
+    /// Validate a check digit, see [`decimal::valid`]
+    fn luhn_valid(&self) -> bool;
+
+    /// Compute a check digit as an ASCII byte, see [`decimal::checksum`]
+    fn luhn_checksum(&self) -> Option<u8>;
+}
+
+/// Alphanumeric Luhn checks using method syntax
+///
+/// Implemented for byte slices, strings and byte arrays, results are the same as
+/// [`alphanum::valid`] and [`alphanum::checksum`].
+///
+/// # Examples
+/// ```rust
+///    use luhn3::LuhnAlnum;
+///    assert!("US5949181045".luhn_alnum_valid());
+///    assert_eq!(Some(b'5'), "US594918104".luhn_alnum_checksum());
+/// ```
+pub trait LuhnAlnum {
+    /// Validate a check digit, see [`alphanum::valid`]
+    fn luhn_alnum_valid(&self) -> bool;
+
+    /// Compute a check digit as an ASCII byte, see [`alphanum::checksum`]
+    fn luhn_alnum_checksum(&self) -> Option<u8>;
+}
+
+impl Luhn for [u8] {
+    fn luhn_valid(&self) -> bool {
+        decimal::valid(self)
+    }
+
+    fn luhn_checksum(&self) -> Option<u8> {
+        decimal::checksum(self)
+    }
+}
+
+impl Luhn for str {
+    fn luhn_valid(&self) -> bool {
+        decimal::valid(self.as_bytes())
+    }
+
+    fn luhn_checksum(&self) -> Option<u8> {
+        decimal::checksum(self.as_bytes())
+    }
+}
+
+impl<const N: usize> Luhn for [u8; N] {
+    fn luhn_valid(&self) -> bool {
+        decimal::valid_arr(self)
+    }
+
+    fn luhn_checksum(&self) -> Option<u8> {
+        decimal::checksum(self)
+    }
+}
+
+impl LuhnAlnum for [u8] {
+    fn luhn_alnum_valid(&self) -> bool {
+        alphanum::valid(self)
+    }
+
+    fn luhn_alnum_checksum(&self) -> Option<u8> {
+        alphanum::checksum(self)
+    }
+}
+
+impl LuhnAlnum for str {
+    fn luhn_alnum_valid(&self) -> bool {
+        alphanum::valid(self.as_bytes())
+    }
+
+    fn luhn_alnum_checksum(&self) -> Option<u8> {
+        alphanum::checksum(self.as_bytes())
+    }
+}
+
+impl<const N: usize> LuhnAlnum for [u8; N] {
+    fn luhn_alnum_valid(&self) -> bool {
+        alphanum::valid_arr(self)
+    }
+
+    fn luhn_alnum_checksum(&self) -> Option<u8> {
+        alphanum::checksum(self)
+    }
+}
+
+/// Iterator adapter yielding only items with a valid check digit
+///
+/// Created by [`LuhnIter::luhn_valid_decimal`] and [`LuhnIter::luhn_valid_alphanum`]
+#[derive(Debug, Clone)]
+pub struct LuhnValidated<I> {
+    iter: I,
+    valid: fn(&[u8]) -> bool,
+}
+
+impl<I> Iterator for LuhnValidated<I>
+where
+    I: Iterator,
+    I::Item: AsRef<[u8]>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let valid = self.valid;
+        self.iter.find(|item| valid(item.as_ref()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+/// Lazy filtering of iterators over byte slices or strings by Luhn validity
+///
+/// # Examples
+/// ```rust
+///    use luhn3::LuhnIter;
+///    let input = ["4012888888881881", "4012888888881882", "US5949181045"];
+///    let decimal = input.iter().luhn_valid_decimal().collect::<Vec<_>>();
+///    assert_eq!(decimal, [&"4012888888881881"]);
+///    let alphanum = input.iter().luhn_valid_alphanum().collect::<Vec<_>>();
+///    assert_eq!(alphanum, [&"4012888888881881", &"US5949181045"]);
+/// ```
+pub trait LuhnIter: Iterator + Sized
+where
+    Self::Item: AsRef<[u8]>,
+{
+    /// Keep only items passing [`decimal::valid`]
+    fn luhn_valid_decimal(self) -> LuhnValidated<Self> {
+        LuhnValidated {
+            iter: self,
+            valid: decimal::valid,
+        }
+    }
+
+    /// Keep only items passing [`alphanum::valid`]
+    fn luhn_valid_alphanum(self) -> LuhnValidated<Self> {
+        LuhnValidated {
+            iter: self,
+            valid: alphanum::valid,
+        }
+    }
+}
+
+impl<I> LuhnIter for I
+where
+    I: Iterator,
+    I::Item: AsRef<[u8]>,
+{
+}
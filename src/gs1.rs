@@ -0,0 +1,71 @@
+//! # GS1 identification keys
+//!
+//! GS1 keys such as GLN, GTIN-13, GTIN-14, GSIN and SSCC use a mod 10 check digit.
+//! Unlike Luhn digits are multiplied by 3 and 1 alternatively starting from the right
+//! most digit of the body, there's no digit sum of the products involved.
+//!
+//! ```
+//! use luhn3::gs1;
+//! // SSCC-18
+//! assert!(gs1::valid(b"106141411234567897"));
+//! ```
+
+/// Validate a GS1 key of 13, 14, 17 or 18 digits
+///
+/// Supported lengths are GLN and GTIN-13 (13), GTIN-14 (14), GSIN (17) and SSCC (18),
+/// returns `false` for any other length or non decimal input.
+///
+/// ```
+/// use luhn3::gs1::valid;
+///
+/// // GTIN-13
+/// assert!(valid(b"4006381333931"));
+/// // GTIN-14
+/// assert!(valid(b"10614141000415"));
+/// // SSCC-18
+/// assert!(valid(b"376104250021234569"));
+///
+/// // wrong check digit
+/// assert!(!valid(b"4006381333932"));
+///
+/// // unsupported length
+/// assert!(!valid(b"96385074"));
+/// ```
+pub fn valid(id: &[u8]) -> bool {
+    if !matches!(id.len(), 13 | 14 | 17 | 18) {
+        return false;
+    }
+    let mut sum = 0u32;
+    for (i, c) in id.iter().rev().enumerate() {
+        let digit = c.wrapping_sub(b'0');
+        if digit > 9 {
+            return false;
+        }
+        let weight = if i % 2 == 1 { 3 } else { 1 };
+        sum += u32::from(digit) * weight;
+    }
+    sum.is_multiple_of(10)
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn test_gs1_lengths() {
+        for id in [
+            &b"4006381333931"[..],
+            b"10614141000415",
+            b"06141411234567890",
+            b"106141411234567897",
+        ] {
+            assert!(super::valid(id));
+            let mut s = Vec::from(id);
+            s[2] = b'0' + (s[2] - b'0' + 1) % 10;
+            assert!(!super::valid(&s));
+            s[2] = b'x';
+            assert!(!super::valid(&s));
+        }
+        // valid 3/1 weighted check digit but lengths are not supported
+        assert!(!super::valid(b"0"));
+        assert!(!super::valid(b"0000000000000000"));
+    }
+}
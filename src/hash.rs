@@ -0,0 +1,116 @@
+use crate::Mixer;
+use core::hash::Hasher;
+
+/// [`Hasher`] wrapper that validates hashed decimal digits
+///
+/// All the writes are forwarded to the inner hasher unchanged so hash values are the same
+/// as without the wrapper. Bytes passed to [`Hasher::write`] are also fed into a [`Mixer`],
+/// anything other than ASCII digits marks the input as invalid. Integer writes such as
+/// length prefixes or string terminators are only forwarded.
+///
+/// # Examples
+/// ```rust
+///    use core::hash::{Hash, Hasher};
+///    use luhn3::LuhnHasher;
+///    use std::collections::hash_map::DefaultHasher;
+///
+///    let mut h = LuhnHasher::new(DefaultHasher::new());
+///    "4012888888881881".hash(&mut h);
+///    assert!(h.valid());
+///
+///    let mut plain = DefaultHasher::new();
+///    "4012888888881881".hash(&mut plain);
+///    assert_eq!(plain.finish(), h.finish());
+/// ```
+pub struct LuhnHasher<H> {
+    inner: H,
+    mixer: Mixer,
+    invalid: bool,
+}
+
+impl<H> LuhnHasher<H> {
+    /// Wrap a hasher
+    pub fn new(inner: H) -> Self {
+        Self {
+            inner,
+            mixer: Mixer::default(),
+            invalid: false,
+        }
+    }
+
+    /// Check if hashed bytes form a decimal number with a valid check digit
+    pub fn valid(&self) -> bool {
+        !self.invalid && self.mixer.valid()
+    }
+
+    /// Unwrap the inner hasher
+    pub fn into_inner(self) -> H {
+        self.inner
+    }
+}
+
+impl<H: Default> Default for LuhnHasher<H> {
+    fn default() -> Self {
+        Self::new(H::default())
+    }
+}
+
+macro_rules! forward {
+    ($($name:ident: $ty:ty),*) => {
+        $(
+            fn $name(&mut self, i: $ty) {
+                self.inner.$name(i)
+            }
+        )*
+    };
+}
+
+impl<H: Hasher> Hasher for LuhnHasher<H> {
+    fn finish(&self) -> u64 {
+        self.inner.finish()
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.inner.write(bytes);
+        for c in bytes {
+            if c.is_ascii_digit() {
+                self.mixer.push(c - b'0');
+            } else {
+                self.invalid = true;
+            }
+        }
+    }
+
+    forward!(
+        write_u8: u8, write_u16: u16, write_u32: u32, write_u64: u64, write_u128: u128,
+        write_usize: usize, write_i8: i8, write_i16: i16, write_i32: i32, write_i64: i64,
+        write_i128: i128, write_isize: isize
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::LuhnHasher;
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    fn hash<T: Hash + ?Sized>(value: &T) -> (u64, bool) {
+        let mut h = LuhnHasher::<DefaultHasher>::default();
+        value.hash(&mut h);
+        (h.finish(), h.valid())
+    }
+
+    #[test]
+    fn test_hash_and_validate() {
+        let (a, valid) = hash("4012888888881881");
+        assert!(valid);
+        assert_eq!((a, true), hash("4012888888881881"));
+        assert!(hash(&b"4012888888881881"[..]).1);
+        let (b, valid) = hash("4012888888881882");
+        assert!(!valid);
+        assert_ne!(a, b);
+        assert_eq!((b, false), hash("4012888888881882"));
+        assert!(!hash("4012 8888 8888 1881").1);
+        assert!(!hash("").1);
+    }
+}
@@ -0,0 +1,70 @@
+//! # SIM card numbers
+//!
+//! Integrated Circuit Card Identifier is 19 or 20 digits long, starts with `89` - major
+//! industry identifier for telecommunications, and ends with a Luhn check digit.
+//!
+//! ```
+//! use luhn3::iccid;
+//! assert!(iccid::valid(b"89014103211118510720"));
+//! assert!(!iccid::valid(b"89014103211118510721"));
+//! ```
+use crate::decimal;
+
+/// Major industry identifier for telecommunications
+const PREFIX: &[u8; 2] = b"89";
+
+/// Validate a 19 or 20 digit ICCID
+///
+/// ```
+/// use luhn3::iccid::valid;
+///
+/// assert!(valid(b"89014103211118510720"));
+/// assert!(valid(b"8944500102198304826"));
+///
+/// // valid check digit but not a telecom number
+/// assert!(!valid(b"4012888888881888883"));
+/// ```
+pub fn valid(iccid: &[u8]) -> bool {
+    matches!(iccid.len(), 19 | 20) && iccid.starts_with(PREFIX) && decimal::valid(iccid)
+}
+
+/// Compute a check digit for the first 18 or 19 digits of ICCID
+///
+/// ```
+/// use luhn3::iccid::check_digit;
+///
+/// assert_eq!(Some(b'0'), check_digit(b"8901410321111851072"));
+///
+/// // body must start with 89 and be 18 or 19 digits long
+/// assert_eq!(None, check_digit(b"9014103211118510720"));
+/// assert_eq!(None, check_digit(b"89014103211118510"));
+/// ```
+pub fn check_digit(body: &[u8]) -> Option<u8> {
+    if !matches!(body.len(), 18 | 19) || !body.starts_with(PREFIX) {
+        return None;
+    }
+    decimal::checksum(body)
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn test_iccid() {
+        for iccid in [&b"89014103211118510720"[..], b"8944500102198304826"] {
+            assert!(super::valid(iccid));
+            let (check, body) = iccid.split_last().unwrap();
+            assert_eq!(Some(*check), super::check_digit(body));
+            let mut s = iccid.to_vec();
+            s[1] = b'8';
+            crate::decimal::set_check_digit(&mut s);
+            assert!(!super::valid(&s));
+        }
+        for len in 2..=21 {
+            let mut s = vec![b'8'; len];
+            s[1] = b'9';
+            crate::decimal::set_check_digit(&mut s);
+            assert_eq!(matches!(len, 19 | 20), super::valid(&s));
+        }
+        assert!(!super::valid(b"8901410321111851072x"));
+    }
+}
@@ -1,5 +1,9 @@
 #![doc = include_str!("../README.md")]
-#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
+#![cfg_attr(not(any(feature = "simd", feature = "capi")), forbid(unsafe_code))]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
 /// Digit mixer for one symbol at a time consuming.
 ///
@@ -27,8 +31,11 @@
 /// are defined in terms of the right most digit [`Mixer`] tries to keep
 /// enough information available to be able to perform the final calculation
 /// for both even and odd sized transmogrified strings.
+///
+/// Sums are kept in `u64` on all platforms so they can't overflow for any realistic
+/// input, [`Mixer::len`] saturates at `usize::MAX`.
 #[derive(Default)]
-pub struct Mixer(Blob, Blob);
+pub struct Mixer(Blob, Blob, usize);
 
 impl Mixer {
     /// Add a new digit to current checksum computation
@@ -46,24 +53,225 @@ impl Mixer {
         if digit >= 5 {
             self.0.five_or_higher += 1;
         }
-        self.0.sum += usize::from(digit);
+        self.0.sum += u64::from(digit);
+        self.2 = self.2.saturating_add(1);
         core::mem::swap(&mut self.0, &mut self.1);
     }
 
+    /// Add a new capital ASCII letter to current checksum computation
+    ///
+    /// Input must be in `b'A'..=b'Z'` range, letters represent numbers `10..=35` and are
+    /// pushed as two digits so [`Mixer::len`] increases by 2.
+    ///
+    /// # Panics
+    /// Function contains [debug_assert] to ensure correct input
+    ///
+    /// ```rust
+    ///    use luhn3::Mixer;
+    ///    let mut m = Mixer::default();
+    ///    for c in b"US5949181045" {
+    ///        if c.is_ascii_digit() {
+    ///            m.push(c - b'0');
+    ///        } else {
+    ///            m.push_letter(*c);
+    ///        }
+    ///    }
+    ///    assert!(m.valid());
+    /// ```
+    #[inline(always)]
+    pub fn push_letter(&mut self, letter: u8) {
+        debug_assert!(letter.is_ascii_uppercase());
+        let value = letter - b'A' + 10;
+        self.push(value / 10);
+        self.push(value % 10);
+    }
+
+    /// Add a new ASCII digit to current checksum computation, limiting the total length
+    ///
+    /// This is the safe way to feed untrusted streaming input into a [`Mixer`]: bytes other
+    /// than `b'0'..=b'9'` are rejected instead of triggering a [debug_assert] and no more
+    /// than `max_len` digits are accepted. Mixer stays unchanged when an error is returned.
+    ///
+    /// ```rust
+    ///    use luhn3::{Mixer, MixerError};
+    ///    let mut m = Mixer::default();
+    ///    for c in b"4012888888881881" {
+    ///        m.push_checked(*c, 16).unwrap();
+    ///    }
+    ///    assert!(m.valid());
+    ///    assert_eq!(Err(MixerError::TooLong), m.push_checked(b'0', 16));
+    ///    assert_eq!(Err(MixerError::InvalidByte(b'x')), m.push_checked(b'x', 32));
+    ///    assert!(m.valid());
+    /// ```
+    pub fn push_checked(&mut self, byte: u8, max_len: usize) -> Result<(), MixerError> {
+        let digit = byte.wrapping_sub(b'0');
+        if digit > 9 {
+            return Err(MixerError::InvalidByte(byte));
+        }
+        if self.len() >= max_len {
+            return Err(MixerError::TooLong);
+        }
+        self.push(digit);
+        Ok(())
+    }
+
+    /// Number of digits pushed so far
+    pub fn len(&self) -> usize {
+        self.2
+    }
+
+    /// Check if no digits were pushed yet
+    pub fn is_empty(&self) -> bool {
+        self.2 == 0
+    }
+
+    /// Check if the last pushed digit is a valid check digit
+    ///
+    /// An empty mixer doesn't contain a check digit and is never valid, unlike
+    /// [`decimal::valid`] which accepts empty input.
+    ///
+    /// ```rust
+    ///    use luhn3::Mixer;
+    ///    let mut m = Mixer::default();
+    ///    assert!(!m.valid());
+    ///    m.push(0);
+    ///    assert!(m.valid());
+    /// ```
     pub fn valid(&self) -> bool {
-        (self.0.sum * 2 - self.0.five_or_higher * 9 + self.1.sum) % 10 == 0
+        !self.is_empty()
+            && (self.0.sum * 2 - self.0.five_or_higher * 9 + self.1.sum).is_multiple_of(10)
     }
 
+    /// Compute a check digit for pushed digits as an ASCII byte
+    ///
+    /// Result is the same as [`decimal::checksum`] would give for the pushed digits
+    /// regardless of their count being odd or even, pushing it makes [`Mixer::valid`] return
+    /// `true`. An empty mixer gets `b'0'` as a check digit.
+    ///
+    /// ```rust
+    ///    use luhn3::Mixer;
+    ///    let mut m = Mixer::default();
+    ///    assert_eq!(m.checksum(), b'0');
+    ///    for d in [3, 7, 8, 2, 8, 2, 2, 4, 6, 3, 1, 0, 0, 0] {
+    ///        m.push(d);
+    ///    }
+    ///    assert_eq!(m.len(), 14);
+    ///    assert_eq!(m.checksum(), b'5');
+    /// ```
     pub fn checksum(&self) -> u8 {
         let checksum = self.1.sum * 2 - self.1.five_or_higher * 9 + self.0.sum;
         b'0' + ((10 - (checksum % 10)) % 10) as u8
     }
+
+    /// Combine mixers for two adjacent parts of a number, `self` being the left one
+    ///
+    /// Which digits of the left part are doubled depends on the number of digits to the
+    /// right of it, [`Mixer`] keeps track of that so combining gives the same result as
+    /// pushing all the digits into a single mixer. This allows to fold parts of a long
+    /// number independently.
+    ///
+    /// ```rust
+    ///    use luhn3::Mixer;
+    ///    let mut left = Mixer::default();
+    ///    let mut right = Mixer::default();
+    ///    for d in [4, 0, 1, 2, 8, 8, 8, 8] {
+    ///        left.push(d);
+    ///    }
+    ///    for d in [8, 8, 8, 8, 1, 8, 8] {
+    ///        right.push(d);
+    ///    }
+    ///    let mixer = left.combine(right);
+    ///    assert_eq!(mixer.len(), 15);
+    ///    assert_eq!(mixer.checksum(), b'1');
+    /// ```
+    pub fn combine(self, right: Mixer) -> Mixer {
+        let (left0, left1) = if right.2.is_multiple_of(2) {
+            (self.0, self.1)
+        } else {
+            (self.1, self.0)
+        };
+        Mixer(
+            left0.add(right.0),
+            left1.add(right.1),
+            self.2.saturating_add(right.2),
+        )
+    }
+
+    /// Raw state of the mixer for persisting a partially folded number
+    ///
+    /// Contains sums and counts of digits five or higher for both parities followed by
+    /// [`Mixer::len`], [`Mixer::from_state`] restores the mixer so it can continue folding.
+    ///
+    /// ```rust
+    ///    use luhn3::Mixer;
+    ///    let mut m = Mixer::default();
+    ///    for d in [4, 0, 1, 2, 8, 8, 8, 8] {
+    ///        m.push(d);
+    ///    }
+    ///    let mut m = Mixer::from_state(m.to_state());
+    ///    for d in [8, 8, 8, 8, 1, 8, 8, 1] {
+    ///        m.push(d);
+    ///    }
+    ///    assert!(m.valid());
+    /// ```
+    pub fn to_state(&self) -> [u64; 5] {
+        [
+            self.0.sum,
+            self.0.five_or_higher,
+            self.1.sum,
+            self.1.five_or_higher,
+            self.2 as u64,
+        ]
+    }
+
+    /// Restore a mixer from a state produced by [`Mixer::to_state`]
+    ///
+    /// State that didn't come from [`Mixer::to_state`] gives meaningless results. Length
+    /// saturates at `usize::MAX` on 32bit targets.
+    pub fn from_state(state: [u64; 5]) -> Self {
+        let [sum0, five0, sum1, five1, len] = state;
+        Mixer(
+            Blob {
+                sum: sum0,
+                five_or_higher: five0,
+            },
+            Blob {
+                sum: sum1,
+                five_or_higher: five1,
+            },
+            usize::try_from(len).unwrap_or(usize::MAX),
+        )
+    }
 }
 
+mod error;
+mod ext;
+mod hash;
+mod rolling;
+mod scan;
+mod validated;
+mod writer;
+pub use crate::error::{LuhnError, MixerError};
+pub use crate::ext::{Luhn, LuhnAlnum, LuhnIter, LuhnValidated};
+pub use crate::hash::LuhnHasher;
+pub use crate::rolling::RollingLuhn;
+pub use crate::scan::Scan;
+pub use crate::validated::{ValidatedAlphanum, ValidatedDecimal};
+pub use crate::writer::LuhnWriter;
+
 #[derive(Default, Copy, Clone)]
 struct Blob {
-    sum: usize,
-    five_or_higher: usize,
+    sum: u64,
+    five_or_higher: u64,
+}
+
+impl Blob {
+    fn add(self, other: Blob) -> Blob {
+        Blob {
+            sum: self.sum + other.sum,
+            five_or_higher: self.five_or_higher + other.five_or_higher,
+        }
+    }
 }
 
 #[inline(always)]
@@ -82,6 +290,10 @@ fn copy_from_small_slice(buf: &mut [u8; 8], c: &[u8]) {
     }
 }
 
+/// Sum of Luhn transformed decimal digits, 8 digits at a time
+///
+/// Chunks are consumed starting from the right most one, returns `None` as soon as a chunk
+/// with a non decimal byte is found without looking at the remaining ones
 #[inline(always)]
 fn fold10_swar(mask1: u64, mask2: u64, raw: &[u8]) -> Option<u64> {
     let mut sum = 0;
@@ -111,41 +323,284 @@ fn fold10_swar(mask1: u64, mask2: u64, raw: &[u8]) -> Option<u64> {
     Some(sum)
 }
 
+#[cfg(all(
+    feature = "simd",
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2"
+))]
+mod sse2;
+
+/// Inputs of at most this many bytes are folded with [`fold10_swar`] even when a vectorized
+/// implementation is available
+///
+/// Such inputs fit into a single SWAR chunk and setting up SSE2 registers costs more than it
+/// saves, benchmarks across lengths from 4 to 64 show both taking the same time at 8 bytes
+/// and SSE2 being faster for anything longer.
+#[cfg(all(
+    feature = "simd",
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2"
+))]
+const SHORT_INPUT: usize = 8;
+
+/// Sum of Luhn transformed decimal digits using the best available vectorized implementation
+///
+/// Falls back to [`fold10_swar`] on targets without SSE2, with `simd` feature disabled or for
+/// inputs no longer than [`SHORT_INPUT`], inputs fitting into a single register skip the loop
+#[inline(always)]
+fn fold10v(correct: bool, raw: &[u8]) -> Option<u64> {
+    #[cfg(all(
+        feature = "simd",
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "sse2"
+    ))]
+    {
+        if raw.len() > SHORT_INPUT {
+            return if raw.len() <= 16 {
+                sse2::fold10v_short(correct, raw)
+            } else {
+                sse2::fold10v_sse2(correct, raw)
+            };
+        }
+    }
+    if correct {
+        fold10_swar(0x0102010201020102, 0x047f047f047f047f, raw)
+    } else {
+        fold10_swar(0x0201020102010201, 0x7f047f047f047f04, raw)
+    }
+}
+
+/// Sum of Luhn transformed decimal digits folded left to right
+///
+/// Same dispatch as [`fold10v`] but inputs longer than 16 bytes are consumed in order.
+/// Shorter ones fit in a single chunk or register so direction doesn't matter for them
 #[inline(always)]
-fn fold36(mut correct: bool, raw: &[u8]) -> Option<usize> {
-    const LUT_DIGIT: [u8; 10] = [0, 1, 2, 3, 4, 6, 7, 8, 9, 0];
-    const LUT_LETTER_T: [u8; 26] = [
+fn fold10v_forward(correct: bool, raw: &[u8]) -> Option<u64> {
+    #[cfg(all(
+        feature = "simd",
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "sse2"
+    ))]
+    {
+        if raw.len() > SHORT_INPUT {
+            return if raw.len() <= 16 {
+                sse2::fold10v_short(correct, raw)
+            } else {
+                sse2::fold10v_forward_sse2(correct, raw)
+            };
+        }
+    }
+    if correct {
+        fold10_swar(0x0102010201020102, 0x047f047f047f047f, raw)
+    } else {
+        fold10_swar(0x0201020102010201, 0x7f047f047f047f04, raw)
+    }
+}
+
+pub mod tables {
+    //! # Lookup tables used by Luhn folds
+    //!
+    //! Luhn algorithm doubles every second digit starting from the right and replaces
+    //! values above 9 with a sum of their digits, letters `A..=Z` stand for numbers
+    //! `10..=35` and contribute two digits each.
+
+    /// Contribution of a doubled decimal digit
+    ///
+    /// ```
+    /// use luhn3::tables::DOUBLE_LUT;
+    /// // 7 * 2 = 14, 1 + 4 = 5
+    /// assert_eq!(DOUBLE_LUT[7], 5);
+    /// ```
+    pub const DOUBLE_LUT: [u8; 10] = [0, 2, 4, 6, 8, 1, 3, 5, 7, 9];
+
+    /// Contribution of a letter when the lower of its two digits is doubled
+    ///
+    /// ```
+    /// use luhn3::tables::LETTER_LUT_T;
+    /// // 'B' is 11, doubled 1 gives 2 plus 1
+    /// assert_eq!(LETTER_LUT_T[(b'B' - b'A') as usize], 3);
+    /// ```
+    pub const LETTER_LUT_T: [u8; 26] = [
         1, 3, 5, 7, 9, 2, 4, 6, 8, 10, 2, 4, 6, 8, 10, 3, 5, 7, 9, 11, 3, 5, 7, 9, 11, 4,
     ];
-    const LUT_LETTER_F: [u8; 26] = [
+
+    /// Contribution of a letter when the higher of its two digits is doubled
+    ///
+    /// ```
+    /// use luhn3::tables::LETTER_LUT_F;
+    /// // 'B' is 11, 1 plus doubled 1 gives 3
+    /// assert_eq!(LETTER_LUT_F[(b'B' - b'A') as usize], 3);
+    /// ```
+    pub const LETTER_LUT_F: [u8; 26] = [
         2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 6, 7, 8, 9, 10, 11,
     ];
+}
+
+/// Check if all the bytes are decimal digits or capital ASCII letters
+///
+/// Uses SSE2 when available and `simd` feature is enabled
+#[inline(always)]
+fn all_alphanum(raw: &[u8]) -> bool {
+    #[cfg(all(
+        feature = "simd",
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "sse2"
+    ))]
+    {
+        sse2::all_alphanum_sse2(raw)
+    }
+    #[cfg(not(all(
+        feature = "simd",
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "sse2"
+    )))]
+    {
+        raw.iter()
+            .all(|c| c.is_ascii_digit() || c.is_ascii_uppercase())
+    }
+}
+
+/// Index of the first byte that is a decimal digit if `digit` is set or isn't otherwise
+///
+/// Uses SSE2 when available and `simd` feature is enabled
+#[inline(always)]
+fn find_digit(raw: &[u8], digit: bool) -> Option<usize> {
+    #[cfg(all(
+        feature = "simd",
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "sse2"
+    ))]
+    {
+        sse2::find_digit_sse2(raw, digit)
+    }
+    #[cfg(not(all(
+        feature = "simd",
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "sse2"
+    )))]
+    {
+        raw.iter().position(|c| c.is_ascii_digit() == digit)
+    }
+}
+
+/// Sum of Luhn transformed decimal digits given as values in `0..=9` range
+///
+/// Digits are consumed starting from the right most one
+#[inline(always)]
+fn fold10_iter<I: Iterator<Item = u8>>(mut correct: bool, digits: I) -> u64 {
+    use crate::tables::DOUBLE_LUT;
+    let mut acc = 0;
+    for digit in digits {
+        debug_assert!(digit < 10);
+        if correct {
+            acc += u64::from(DOUBLE_LUT[digit as usize]);
+        } else {
+            acc += u64::from(digit);
+        }
+        correct = !correct;
+    }
+    acc
+}
+
+/// Sum of Luhn transformed ASCII decimal digits
+///
+/// Digits are consumed starting from the right most one, returns `None` on non decimal input
+#[inline(always)]
+fn fold10_ascii<I: Iterator<Item = u8>>(mut correct: bool, ascii: I) -> Option<u64> {
+    use crate::tables::DOUBLE_LUT;
+    let mut acc = 0;
+    for c in ascii {
+        let digit = c.wrapping_sub(b'0');
+        if digit > 9 {
+            return None;
+        }
+        if correct {
+            acc += u64::from(DOUBLE_LUT[digit as usize]);
+        } else {
+            acc += u64::from(digit);
+        }
+        correct = !correct;
+    }
+    Some(acc)
+}
+
+/// Contribution of a letter `0..26` into the Luhn sum, `correct` is set when the lower of
+/// its two digits is doubled
+#[cfg(not(feature = "compact"))]
+#[inline(always)]
+fn letter_contribution(letter: u8, correct: bool) -> u8 {
+    use crate::tables::{LETTER_LUT_F, LETTER_LUT_T};
+    if correct {
+        LETTER_LUT_T[letter as usize]
+    } else {
+        LETTER_LUT_F[letter as usize]
+    }
+}
+
+/// Contribution of a letter `0..26` into the Luhn sum, `correct` is set when the lower of
+/// its two digits is doubled
+///
+/// Computed from the letter value with `compact` feature instead of looking up letter
+/// tables
+#[cfg(feature = "compact")]
+#[inline(never)]
+fn letter_contribution(letter: u8, correct: bool) -> u8 {
+    let tens = 1 + u8::from(letter >= 10) + u8::from(letter >= 20);
+    let ones = letter + 10 - tens * 10;
+    if correct {
+        tens + ones * 2 - u8::from(ones >= 5) * 9
+    } else {
+        tens * 2 + ones
+    }
+}
+
+/// Sum of Luhn transformed alphanumeric symbols
+///
+/// Each symbol adds at most 13 to the sum which is kept in `u64` even on 32bit targets
+/// where `usize` could overflow after a few hundred megabytes of input.
+#[inline(always)]
+fn fold36(mut correct: bool, raw: &[u8]) -> Option<u64> {
+    use crate::tables::*;
     let mut acc = 0;
 
     for c in raw.iter().copied().rev() {
         match c {
             b'0'..=b'9' => {
-                let digit = (c - b'0') as usize;
-                acc += digit;
-                if correct {
-                    acc += LUT_DIGIT[digit] as usize;
-                }
-                correct = !correct;
-            }
-            b'A'..=b'Z' => {
-                let letter = (c - b'A') as usize;
+                let digit = c - b'0';
                 if correct {
-                    acc += LUT_LETTER_T[letter] as usize;
+                    acc += u64::from(DOUBLE_LUT[digit as usize]);
                 } else {
-                    acc += LUT_LETTER_F[letter] as usize;
+                    acc += u64::from(digit);
                 }
+                correct = !correct;
             }
+            b'A'..=b'Z' => acc += u64::from(letter_contribution(c - b'A', correct)),
             _ => return None,
         }
     }
     Some(acc)
 }
 
+/// Sum of Luhn transformed alphanumeric symbols using the best available vectorized
+/// implementation
+///
+/// Falls back to [`fold36`] on targets without SSE2, with `simd` feature disabled or for
+/// inputs no longer than [`SHORT_INPUT`]
+#[inline(always)]
+fn fold36v(correct: bool, raw: &[u8]) -> Option<u64> {
+    #[cfg(all(
+        feature = "simd",
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "sse2"
+    ))]
+    {
+        if raw.len() > SHORT_INPUT {
+            return sse2::fold36_sse2(correct, raw);
+        }
+    }
+    fold36(correct, raw)
+}
+
 pub mod decimal {
     //! # Operations on decimal only Luhn checksums
     //!
@@ -154,7 +609,7 @@ pub mod decimal {
     //! ```
     //! use luhn3::decimal as luhn;
     //! // test Visa card number is valid
-    //! assert!(luhn3::valid(b"4012888888881881"));
+    //! assert!(luhn::valid(b"4012888888881881"));
     //! ```
     //!
     //! See also [alphanum][crate::alphanum]
@@ -164,8 +619,18 @@ pub mod decimal {
     ///
     /// Takes a slice of ASCII bytes and checks if the last byte is a valid Luhn checksum digit.
     /// Will return `false` if checksum digit valid but input is not a decimal only - for example
-    /// an ISIN code. It is safe to pass non ASCII sequences of bytes.
+    /// an ISIN code. It is safe to pass non ASCII sequences of bytes. `\0` is not a digit
+    /// so input with an interior NUL byte is never valid, use [valid_cstr] for NUL
+    /// terminated strings.
+    ///
+    /// Input is processed 8 bytes at a time starting from the right and validation stops at
+    /// the first chunk containing a non decimal byte, so rejecting a long input with garbage
+    /// near the end doesn't look at the rest of it. Same applies to [valid_vec] with 16 byte
+    /// chunks.
     ///
+    /// This function stays scalar even when SSE2 or SSSE3 are enabled at compile time, use
+    /// [valid_vec] for the vectorized implementation. Tests check vectorized folds against
+    /// this one so it must not turn into one of them depending on target features.
     ///
     /// # Usage
     ///
@@ -194,6 +659,109 @@ pub mod decimal {
         }
     }
 
+    /// Validate a check digit of a number stored in a part of a larger buffer
+    ///
+    /// Same as [valid] on `buf[range]` but returns `false` instead of panicking if `range`
+    /// is out of bounds or decreasing.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use luhn3::decimal::valid_range;
+    ///
+    /// let buf = b"card: 4012888888881881, exp: 12/25";
+    /// assert!(valid_range(buf, 6..22));
+    /// assert!(!valid_range(buf, 6..21));
+    /// assert!(!valid_range(buf, 30..40));
+    /// ```
+    pub fn valid_range(buf: &[u8], range: core::ops::Range<usize>) -> bool {
+        buf.get(range).is_some_and(valid)
+    }
+
+    /// Validate a check digit of a NUL terminated string coming from C
+    ///
+    /// Only bytes up to the terminator are validated, same as [valid] on
+    /// [`CStr::to_bytes`](core::ffi::CStr::to_bytes).
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use core::ffi::CStr;
+    /// use luhn3::decimal::valid_cstr;
+    ///
+    /// let number = CStr::from_bytes_until_nul(b"4012888888881881\0garbage").unwrap();
+    /// assert!(valid_cstr(number));
+    ///
+    /// // anything after the first NUL is ignored
+    /// let number = CStr::from_bytes_until_nul(b"40128888\x008881881\0").unwrap();
+    /// assert!(!valid_cstr(number));
+    /// ```
+    pub fn valid_cstr(number: &core::ffi::CStr) -> bool {
+        valid(number.to_bytes())
+    }
+
+    /// Validate a check digit using Luhn algorithm in a `const` context
+    ///
+    /// Same results as [valid] but processes one byte at a time so it can be evaluated at
+    /// compile time, see [`assert_valid`]. Prefer [valid] at runtime.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use luhn3::decimal::valid_const;
+    ///
+    /// const VISA: bool = valid_const(b"4012888888881881");
+    /// assert!(VISA);
+    /// assert!(!valid_const(b""));
+    /// ```
+    pub const fn valid_const(ascii: &[u8]) -> bool {
+        let mut sum = 0u64;
+        let mut ix = ascii.len();
+        let mut double = false;
+        while ix > 0 {
+            ix -= 1;
+            let digit = ascii[ix].wrapping_sub(b'0');
+            if digit > 9 {
+                return false;
+            }
+            sum += if double {
+                tables::DOUBLE_LUT[digit as usize]
+            } else {
+                digit
+            } as u64;
+            double = !double;
+        }
+        !ascii.is_empty() && sum.is_multiple_of(10)
+    }
+
+    /// Validate a check digit using Luhn algorithm and report the reason for failure
+    ///
+    /// Same as [valid] but returns a [`LuhnError`] describing what is wrong with the input,
+    /// [`LuhnError::InvalidByte`] contains index of the first non decimal byte. With
+    /// `tracing` or `defmt` feature enabled failures are reported as `debug` events.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use luhn3::{decimal::check, LuhnError};
+    ///
+    /// assert_eq!(Ok(()), check(b"4012888888881881"));
+    /// assert_eq!(Err(LuhnError::InvalidChecksum), check(b"4012888888881882"));
+    /// assert_eq!(Err(LuhnError::InvalidByte(0)), check(b"US5949181045"));
+    /// assert_eq!(Err(LuhnError::Empty), check(b""));
+    /// ```
+    pub fn check(ascii: &[u8]) -> Result<(), LuhnError> {
+        let res = match fold10_swar(0x0201020102010201, 0x7f047f047f047f04, ascii) {
+            _ if ascii.is_empty() => Err(LuhnError::Empty),
+            Some(d) if d % 10 == 0 => Ok(()),
+            Some(_) => Err(LuhnError::InvalidChecksum),
+            None => Err(LuhnError::InvalidByte(
+                ascii.iter().position(|c| !c.is_ascii_digit()).unwrap_or(0),
+            )),
+        };
+        crate::error::report(res)
+    }
+
     /// Validate a check digit using Luhn algorithm with input given as an array
     ///
     /// Takes a slice of ASCII bytes and checks if the last byte is a valid Luhn checksum digit.
@@ -251,172 +819,3210 @@ pub mod decimal {
         let sum = fold10_swar(0x0102010201020102, 0x047f047f047f047f, ascii)?;
         Some(b'0' + ((10 - (sum % 10)) % 10) as u8)
     }
-}
-
-pub mod alphanum {
-    //! # Operations on alphanumeric Luhn checksums
-    //!
-    //! This version of the algorithm can operate on decimal digits and capital ASCII letters.
-    //!
-    //! # Usage
-    //!
-    //! ```
-    //! // Microsoft's ISIN is valid
-    //! assert!(luhn3::valid(b"US5949181045"));
-    //! ```
-    //!
-    //! See also [decimal][crate::decimal]
-    use crate::*;
 
-    /// Validate a check digit using Luhn algorithm
+    /// Compute a check digit using Luhn doubling with a custom modulus
     ///
-    /// ```
-    /// use luhn3::alphanum::valid;
+    /// Digits are doubled the same way as in [checksum] but the check digit is
+    /// `(modulus - sum % modulus) % modulus` so the sum of a complete number is divisible by
+    /// `modulus`. Check digit must fit into a single decimal digit so `modulus` must be in
+    /// `1..=10` range, returns `None` otherwise or if the input is not decimal. With
+    /// `modulus` of 10 this is the same as [checksum].
     ///
-    /// // Microsoft's ISIN is valid
-    /// assert!(valid(b"US5949181045"));
+    /// # Usage
     ///
-    /// // Not a valid sequence
-    /// let banana = String::from("banana");
-    /// assert!(!valid(banana.as_bytes()));
+    /// ```
+    /// use luhn3::decimal::{checksum, checksum_mod};
     ///
-    /// // Even less valid sequence
-    /// let noms = "口水鸡";
-    /// assert!(!valid(noms.as_bytes()));
+    /// assert_eq!(checksum(b"401288888888188"), checksum_mod(b"401288888888188", 10));
+    /// assert_eq!(Some(b'2'), checksum_mod(b"401288888888188", 7));
+    /// assert_eq!(None, checksum_mod(b"401288888888188", 11));
     /// ```
-    pub fn valid(ascii: &[u8]) -> bool {
-        match fold36(false, ascii) {
-            Some(v) => v % 10 == 0,
-            None => false,
+    pub fn checksum_mod(body: &[u8], modulus: u8) -> Option<u8> {
+        if !(1..=10).contains(&modulus) {
+            return None;
         }
+        let modulus = u64::from(modulus);
+        // SWAR fold keeps only the remainder modulo 10 intact
+        let sum = fold10_ascii(true, body.iter().rev().copied())?;
+        Some(b'0' + ((modulus - sum % modulus) % modulus) as u8)
     }
 
-    /// Validate a check digit using Luhn algorithm
+    /// Validate a check digit using Luhn algorithm ignoring surrounding whitespace
     ///
-    /// Results are identical to those of [valid] but compiler can optimize this version better
-    /// ```
-    /// use luhn3::alphanum::valid;
+    /// Leading and trailing ASCII whitespace is trimmed before validation, whitespace
+    /// inside the number is still rejected.
     ///
-    /// // Microsoft's ISIN is valid
-    /// assert!(valid(b"US5949181045"));
+    /// # Usage
     ///
-    /// // Not a valid sequence
-    /// let banana = String::from("banana");
-    /// assert!(!valid(banana.as_bytes()));
+    /// ```
+    /// use luhn3::decimal::valid_trimmed;
     ///
-    /// // Even less valid sequence
-    /// let noms = "口水鸡";
-    /// assert!(!valid(noms.as_bytes()));
+    /// // line read from a file
+    /// assert!(valid_trimmed(b"  4111111111111111\n"));
+    ///
+    /// // but not a formatted number
+    /// assert!(!valid_trimmed(b"4111 1111 1111 1111"));
     /// ```
-    pub fn valid_arr<const T: usize>(ascii: &[u8; T]) -> bool {
-        match fold36(false, ascii) {
-            Some(v) => v % 10 == 0,
-            None => false,
-        }
+    pub fn valid_trimmed(ascii: &[u8]) -> bool {
+        valid(ascii.trim_ascii())
     }
 
-    /// Try to compute a check digit for a sequence of ASCII bytes
+    /// Validate a check digit using Luhn algorithm on a single line of input
     ///
-    /// If input contains only bytes in `b'0'..b'9' | b'A'..b'Z'` range output
-    /// is guaranteed to be a byte in `b'0'..=b'9'` range or None otherwise.
-    /// ```
-    /// use luhn3::alphanum::checksum;
+    /// A single trailing `\n` or `\r\n` is stripped before validation, unlike
+    /// [`valid_trimmed`] any other whitespace is rejected.
     ///
-    /// // Can calculate a checksum for a string with decimal digits
-    /// assert_eq!(Some(b'1'), checksum(b"401288888888188"));
+    /// # Usage
     ///
-    /// // Can calculate a checksum for Microsoft's ISIN
-    /// assert_eq!(Some(b'5'), checksum(b"US594918104"));
+    /// ```
+    /// use luhn3::decimal::valid_line;
     ///
-    /// // Not a valid sequence
-    /// let banana = String::from("banana");
-    /// assert_eq!(None, checksum(banana.as_bytes()));
+    /// assert!(valid_line(b"4111111111111111\r\n"));
+    /// assert!(valid_line(b"4111111111111111\n"));
+    /// assert!(valid_line(b"4111111111111111"));
     ///
-    /// // Even less valid sequence
-    /// let noms = "口水鸡";
-    /// assert_eq!(None, checksum(noms.as_bytes()));
+    /// assert!(!valid_line(b"4111111111111111 "));
+    /// assert!(!valid_line(b"4111111111111111\n\n"));
     /// ```
-    pub fn checksum(ascii: &[u8]) -> Option<u8> {
-        let sum = fold36(true, ascii)?;
-        Some(b'0' + ((10 - (sum % 10)) % 10) as u8)
+    pub fn valid_line(ascii: &[u8]) -> bool {
+        let line = ascii.strip_suffix(b"\n").unwrap_or(ascii);
+        let line = if line.len() < ascii.len() {
+            line.strip_suffix(b"\r").unwrap_or(line)
+        } else {
+            line
+        };
+        valid(line)
     }
-}
 
-pub use crate::alphanum::*;
-
-#[cfg(test)]
-mod test {
-    const DECIMAL_LUHN_SAMPLES: &'static [&str] = &[
-        // test cc numbers
-        "378282246310005",  // American Express
-        "371449635398431",  // American Express
-        "378734493671000",  // American Express Corporate
-        "5610591081018250", // Australian BankCard
-        "30569309025904",   // Diners Club
-        "38520000023237",   // Diners Club
-        "6011111111111117", // Discover
-        "6011000990139424", // Discover
-        "3530111333300000", // JCB
-        "3566002020360505", // JCB
-        "5555555555554444", // MasterCard
-        "5105105105105100", // MasterCard
-        "4111111111111111", // Visa
-        "4012888888881881", // Visa
-        "4222222222222",    // Visa
-        "5019717010103742", // Dankort (PBS)
-        "6331101999990016", // Switch/Solo (Paymentech)
-        // random IMEI
-        "358771054102508", // Apple iPad Air (A1475)
-        "867103029110602", // HUAWEI G610-U20
+    /// Count valid and invalid numbers in a stream of newline separated numbers
+    ///
+    /// Each line is validated with [valid_line] semantics, blank lines are skipped. Returns
+    /// `(valid_count, invalid_count)`. Requires `std` feature.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use luhn3::decimal::validate_lines;
+    /// use std::io::Cursor;
+    ///
+    /// let dump = "4012888888881881\r\n4012888888881882\n\n79927398713\n";
+    /// assert_eq!((2, 1), validate_lines(Cursor::new(dump)).unwrap());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn validate_lines<R: std::io::Read>(reader: R) -> std::io::Result<(usize, usize)> {
+        use std::io::BufRead;
+        let mut reader = std::io::BufReader::new(reader);
+        let mut line = Vec::new();
+        let (mut valid, mut invalid) = (0, 0);
+        loop {
+            line.clear();
+            if reader.read_until(b'\n', &mut line)? == 0 {
+                return Ok((valid, invalid));
+            }
+            if matches!(&line[..], b"\n" | b"\r\n") {
+                continue;
+            }
+            if valid_line(&line) {
+                valid += 1;
+            } else {
+                invalid += 1;
+            }
+        }
+    }
+
+    /// Validate a check digit using Luhn algorithm for a number with a length prefix
+    ///
+    /// First byte of the frame is a binary count of digits in the number excluding the
+    /// check digit, it's followed by the digits and the check digit. Returns `false` if the
+    /// frame size doesn't match the declared length.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use luhn3::decimal::valid_framed;
+    ///
+    /// assert!(valid_framed(b"\x0f4012888888881881"));
+    ///
+    /// // declared length doesn't match
+    /// assert!(!valid_framed(b"\x104012888888881881"));
+    /// assert!(!valid_framed(b"\x0e4012888888881881"));
+    /// ```
+    pub fn valid_framed(frame: &[u8]) -> bool {
+        match frame.split_first() {
+            Some((&len, number)) => usize::from(len) + 1 == number.len() && valid(number),
+            None => false,
+        }
+    }
+
+    /// Validate a check digit using Luhn algorithm skipping any of the bytes in `ignore`
+    ///
+    /// Useful for numbers formatted with separators such as spaces, commas or periods.
+    /// `ignore` is not supposed to contain digits, if it does they are skipped as well.
+    ///
+    /// # Panics
+    /// Function contains [debug_assert] to ensure `ignore` contains no digits
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use luhn3::decimal::valid_ignoring;
+    ///
+    /// assert!(valid_ignoring(b"4,012,888,888,881,881", b" ,."));
+    /// assert!(valid_ignoring(b"4012 8888.8888 1881", b" ,."));
+    ///
+    /// // dashes are not in the set
+    /// assert!(!valid_ignoring(b"4012-8888-8888-1881", b" ,."));
+    ///
+    /// // there must be at least one digit
+    /// assert!(!valid_ignoring(b" , ", b" ,."));
+    /// ```
+    pub fn valid_ignoring(ascii: &[u8], ignore: &[u8]) -> bool {
+        debug_assert!(!ignore.iter().any(u8::is_ascii_digit));
+        let mut digits = ascii
+            .iter()
+            .rev()
+            .filter(|c| !ignore.contains(c))
+            .peekable();
+        if digits.peek().is_none() {
+            return false;
+        }
+        match fold10_ascii(false, digits.copied()) {
+            Some(sum) => sum.is_multiple_of(10),
+            None => false,
+        }
+    }
+
+    /// Copy digits of a number formatted with spaces or dashes into `out` and validate them
+    ///
+    /// Returns length of the number written to `out` if it is valid, `None` if it is not,
+    /// contains anything other than digits and separators or doesn't fit into `out`.
+    /// Content of `out` past the returned length is unspecified.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use luhn3::decimal::normalize_and_validate;
+    ///
+    /// let mut buf = [0; 19];
+    /// let len = normalize_and_validate(b"4012 8888 8888 1881", &mut buf).unwrap();
+    /// assert_eq!(b"4012888888881881", &buf[..len]);
+    ///
+    /// // wrong check digit
+    /// assert_eq!(None, normalize_and_validate(b"4012-8888-8888-1882", &mut buf));
+    /// ```
+    pub fn normalize_and_validate(input: &[u8], out: &mut [u8]) -> Option<usize> {
+        let mut len = 0;
+        for &c in input {
+            match c {
+                b'0'..=b'9' => {
+                    *out.get_mut(len)? = c;
+                    len += 1;
+                }
+                b' ' | b'-' => {}
+                _ => return None,
+            }
+        }
+        valid(&out[..len]).then_some(len)
+    }
+
+    /// Validate a check digit using Luhn algorithm on already decoded digits
+    ///
+    /// Takes a slice of digit values in `0..=9` range rather than ASCII bytes, so for `'1'`
+    /// the value should be `1`, same as [`Mixer::push`]. Results are identical to those of
+    /// [valid] on the ASCII representation.
+    ///
+    /// # Panics
+    /// Function contains [debug_assert] to ensure correct input
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use luhn3::decimal::valid_digits;
+    ///
+    /// assert!(valid_digits(&[4, 0, 1, 2, 8, 8, 8, 8, 8, 8, 8, 8, 1, 8, 8, 1]));
+    /// assert!(!valid_digits(&[4, 0, 1, 2, 8, 8, 8, 8, 8, 8, 8, 8, 1, 8, 8, 2]));
+    /// ```
+    pub fn valid_digits(digits: &[u8]) -> bool {
+        !digits.is_empty() && fold10_iter(false, digits.iter().copied().rev()) % 10 == 0
+    }
+
+    /// Compute a check digit for already decoded digits
+    ///
+    /// Takes a slice of digit values in `0..=9` range, see [valid_digits]. Unlike
+    /// [checksum] the result is a digit value in `0..=9` range, not an ASCII byte.
+    ///
+    /// # Panics
+    /// Function contains [debug_assert] to ensure correct input
+    ///
+    /// ```
+    /// use luhn3::decimal::checksum_digits;
+    ///
+    /// assert_eq!(1, checksum_digits(&[4, 0, 1, 2, 8, 8, 8, 8, 8, 8, 8, 8, 1, 8, 8]));
+    /// ```
+    pub fn checksum_digits(digits: &[u8]) -> u8 {
+        let sum = fold10_iter(true, digits.iter().copied().rev());
+        ((10 - (sum % 10)) % 10) as u8
+    }
+
+    /// Minimal number of single digit substitutions required to make a number valid
+    ///
+    /// Returns `Some(0)` for valid numbers. Any residual can be cancelled by replacing
+    /// the check digit alone so for any other decimal number the answer is `Some(1)`.
+    /// Returns `None` for empty or non decimal input.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use luhn3::decimal::edit_distance_to_valid;
+    ///
+    /// assert_eq!(Some(0), edit_distance_to_valid(b"4012888888881881"));
+    /// assert_eq!(Some(1), edit_distance_to_valid(b"4012888888881882"));
+    /// assert_eq!(None, edit_distance_to_valid(b"US5949181045"));
+    /// ```
+    pub fn edit_distance_to_valid(number: &[u8]) -> Option<u8> {
+        if number.is_empty() {
+            return None;
+        }
+        let sum = fold10_swar(0x0201020102010201, 0x7f047f047f047f04, number)?;
+        Some(u8::from(sum % 10 != 0))
+    }
+
+    /// Luhn sum of a number with a check digit modulo 10
+    ///
+    /// Residual of `0` means the number is valid, anything else is how far off the sum is,
+    /// which can be used to chain Luhn with other checks without folding the input again.
+    /// Returns `None` on non decimal input, unlike [valid] empty input gives `Some(0)`.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use luhn3::decimal::residual;
+    ///
+    /// assert_eq!(Some(0), residual(b"4012888888881881"));
+    /// assert_eq!(Some(1), residual(b"4012888888881882"));
+    /// assert_eq!(None, residual(b"US5949181045"));
+    /// ```
+    pub fn residual(number: &[u8]) -> Option<u8> {
+        let sum = fold10_swar(0x0201020102010201, 0x7f047f047f047f04, number)?;
+        Some((sum % 10) as u8)
+    }
+
+    /// Validate a check digit using Luhn algorithm for a number stored in reverse
+    ///
+    /// Takes a slice of ASCII bytes with the least significant digit first, so the first
+    /// byte is the check digit. Results are identical to those of [valid] on the reversed
+    /// input.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use luhn3::decimal::valid_reversed;
+    ///
+    /// assert!(valid_reversed(b"1881888888882104"));
+    /// assert!(!valid_reversed(b"4012888888881881"));
+    /// ```
+    pub fn valid_reversed(ascii: &[u8]) -> bool {
+        !ascii.is_empty() && fold10_ascii(false, ascii.iter().copied()).is_some_and(|s| s % 10 == 0)
+    }
+
+    /// Validate a check digit using Luhn algorithm for a sequence of `char`s
+    ///
+    /// Characters are consumed from the back so the input doesn't need to be collected into
+    /// a buffer, anything other than ASCII digits `'0'..='9'` makes the input invalid,
+    /// including non ASCII digits such as `'٣'`. Results are identical to those of [valid]
+    /// on the UTF-8 encoding of the same characters.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use luhn3::decimal::valid_chars;
+    ///
+    /// assert!(valid_chars("4012888888881881".chars()));
+    ///
+    /// // full width digit
+    /// assert!(!valid_chars("40128888888818８1".chars()));
+    /// assert!(!valid_chars("".chars()));
+    /// ```
+    pub fn valid_chars<I: DoubleEndedIterator<Item = char>>(chars: I) -> bool {
+        let mut empty = true;
+        // non ASCII chars become bytes above 127 which are not digits either
+        let ascii = chars.rev().map(|c| {
+            empty = false;
+            u8::try_from(c).unwrap_or(u8::MAX)
+        });
+        fold10_ascii(false, ascii).is_some_and(|s| s.is_multiple_of(10)) && !empty
+    }
+
+    /// Validate a check digit using either of the doubling conventions
+    ///
+    /// [valid] assumes the right most digit is the check digit and doubles every second
+    /// digit starting from the one next to it. Some systems double starting from the right
+    /// most digit instead, set `double_from_rightmost` to validate such numbers. Empty input
+    /// is never valid.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use luhn3::decimal::valid_variant;
+    ///
+    /// assert!(valid_variant(b"18", false));
+    /// assert!(!valid_variant(b"18", true));
+    /// assert!(valid_variant(b"81", true));
+    /// assert!(!valid_variant(b"81", false));
+    /// ```
+    pub fn valid_variant(ascii: &[u8], double_from_rightmost: bool) -> bool {
+        match fold10v(double_from_rightmost, ascii) {
+            Some(d) => d % 10 == 0 && !ascii.is_empty(),
+            None => false,
+        }
+    }
+
+    /// Fold a chunk of a longer decimal number into a partial sum
+    ///
+    /// Chunks must be folded right to left: `double` tells if the right most digit of the
+    /// chunk should be doubled and must be `false` for the chunk containing the check digit,
+    /// returned parity should be passed to the chunk on the left. Number is valid when the
+    /// sum of partial sums is divisible by 10, only the remainder is meaningful. Returns
+    /// `None` if chunk is not decimal.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use luhn3::decimal::fold_chunk;
+    ///
+    /// let (right, double) = fold_chunk(b"8888881881", false).unwrap();
+    /// let (left, _) = fold_chunk(b"401288", double).unwrap();
+    /// assert_eq!(0, (left + right) % 10);
+    /// ```
+    pub fn fold_chunk(ascii: &[u8], double: bool) -> Option<(u64, bool)> {
+        let sum = if double {
+            fold10_swar(0x0102010201020102, 0x047f047f047f047f, ascii)?
+        } else {
+            fold10_swar(0x0201020102010201, 0x7f047f047f047f04, ascii)?
+        };
+        Some((sum, double ^ (ascii.len() % 2 == 1)))
+    }
+
+    /// Step by step breakdown of a Luhn check digit validation, see [explain]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ExplainResult<'a> {
+        number: &'a [u8],
+        /// Sum of weighted digits
+        pub total: u64,
+        /// Check digit present in the number, as an ASCII byte
+        pub actual: u8,
+        /// Check digit expected for the rest of the number, as an ASCII byte
+        pub expected: u8,
+    }
+
+    impl<'a> ExplainResult<'a> {
+        /// Contributions of each digit to the total, left to right
+        ///
+        /// Every second digit starting from the second to last is doubled and replaced
+        /// with a sum of its digits.
+        pub fn weighted(&self) -> impl Iterator<Item = u8> + 'a {
+            let len = self.number.len();
+            self.number.iter().enumerate().map(move |(i, c)| {
+                let digit = c - b'0';
+                if (len - i).is_multiple_of(2) {
+                    crate::tables::DOUBLE_LUT[digit as usize]
+                } else {
+                    digit
+                }
+            })
+        }
+
+        /// Total modulo 10, `0` for valid numbers
+        pub fn residual(&self) -> u8 {
+            (self.total % 10) as u8
+        }
+
+        /// Check if the number is valid
+        pub fn valid(&self) -> bool {
+            self.actual == self.expected
+        }
+    }
+
+    impl core::fmt::Display for ExplainResult<'_> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            for (i, w) in self.weighted().enumerate() {
+                if i > 0 {
+                    f.write_str(" + ")?;
+                }
+                write!(f, "{}", w)?;
+            }
+            write!(
+                f,
+                " = {}, {} % 10 = {}, check digit {}, expected {}",
+                self.total,
+                self.total,
+                self.residual(),
+                char::from(self.actual),
+                char::from(self.expected)
+            )
+        }
+    }
+
+    /// Explain how the Luhn check digit of a number is validated
+    ///
+    /// Returns per digit weighted values, their total and both the present and the expected
+    /// check digits, [`ExplainResult`] implements [`Display`](core::fmt::Display) for human
+    /// readable output. Returns `None` for empty or non decimal input.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use luhn3::decimal::explain;
+    ///
+    /// let e = explain(b"4111111111111111").unwrap();
+    /// assert_eq!(30, e.total);
+    /// assert_eq!(e.total, e.weighted().map(u64::from).sum());
+    /// assert!(e.valid());
+    ///
+    /// let e = explain(b"4111111111111112").unwrap();
+    /// assert_eq!(1, e.residual());
+    /// assert_eq!(
+    ///     "8 + 1 + 2 + 1 + 2 + 1 + 2 + 1 + 2 + 1 + 2 + 1 + 2 + 1 + 2 + 2 \
+    ///      = 31, 31 % 10 = 1, check digit 2, expected 1",
+    ///     e.to_string()
+    /// );
+    /// ```
+    pub fn explain(number: &[u8]) -> Option<ExplainResult<'_>> {
+        let (&actual, body) = number.split_last()?;
+        let total = fold10_ascii(false, number.iter().copied().rev())?;
+        Some(ExplainResult {
+            number,
+            total,
+            actual,
+            expected: checksum(body)?,
+        })
+    }
+
+    /// Write contribution of each digit of a number with a check digit to the Luhn sum
+    ///
+    /// `out[i]` gets either the digit at `number[i]` or the sum of digits of its double, the
+    /// same values as [`ExplainResult::weighted`]. Returns `None` and leaves `out` unchanged
+    /// if input is not decimal or `out` is shorter than `number`.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use luhn3::decimal::weighted;
+    ///
+    /// let mut out = [0; 4];
+    /// weighted(b"4242", &mut out).unwrap();
+    /// assert_eq!([8, 2, 8, 2], out);
+    /// ```
+    pub fn weighted(number: &[u8], out: &mut [u8]) -> Option<()> {
+        let out = out.get_mut(..number.len())?;
+        if !number.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        for (i, (o, c)) in out.iter_mut().zip(number).enumerate() {
+            let digit = c - b'0';
+            *o = if (number.len() - i).is_multiple_of(2) {
+                tables::DOUBLE_LUT[digit as usize]
+            } else {
+                digit
+            };
+        }
+        Some(())
+    }
+
+    /// Check if a number contains an adjacent transposition Luhn algorithm can't detect
+    ///
+    /// Luhn algorithm catches swaps of any two adjacent digits except `09` and `90`: doubled
+    /// 9 contributes 9 so both orders give the same sum. Returns `true` if `number`
+    /// contains either pair, swapping it would produce a different number that is still
+    /// valid.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use luhn3::decimal::{has_luhn_blindspot, valid};
+    ///
+    /// assert!(has_luhn_blindspot(b"4012888890881887"));
+    /// assert!(valid(b"4012888890881887"));
+    /// assert!(valid(b"4012888809881887"));
+    ///
+    /// assert!(!has_luhn_blindspot(b"4012888888881881"));
+    /// ```
+    pub fn has_luhn_blindspot(number: &[u8]) -> bool {
+        number.windows(2).any(|w| w == b"09" || w == b"90")
+    }
+
+    /// Validate a check digit using Luhn algorithm rejecting repeated digits
+    ///
+    /// Same as [valid] but numbers made of a single repeated digit such as
+    /// `0000000000000000` are rejected since they are most likely placeholders. Repeated
+    /// patterns of more than one digit are accepted.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use luhn3::decimal::{valid, valid_nontrivial};
+    ///
+    /// assert!(valid(b"0000000000000000"));
+    /// assert!(!valid_nontrivial(b"0000000000000000"));
+    ///
+    /// assert!(valid_nontrivial(b"4242424242424242"));
+    /// ```
+    pub fn valid_nontrivial(ascii: &[u8]) -> bool {
+        valid(ascii) && ascii.iter().any(|c| *c != ascii[0])
+    }
+
+    /// Find the first character that is not a decimal digit
+    ///
+    /// Returns index of the character, not the byte, so it can be used to highlight
+    /// the error in UI. Returns `None` if all characters are decimal digits.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use luhn3::decimal::first_invalid_char;
+    ///
+    /// assert_eq!(None, first_invalid_char("4012888888881881"));
+    /// assert_eq!(Some(4), first_invalid_char("4012 8888"));
+    ///
+    /// // 'é' takes two bytes but it's still a single character
+    /// assert_eq!(Some(3), first_invalid_char("401é2888"));
+    ///
+    /// // non ASCII digits are not accepted either
+    /// assert_eq!(Some(4), first_invalid_char("4012８888"));
+    /// ```
+    pub fn first_invalid_char(s: &str) -> Option<usize> {
+        s.chars().position(|c| !c.is_ascii_digit())
+    }
+
+    /// Validate a check digit using Luhn algorithm with a vectorized implementation
+    ///
+    /// Results are identical to those of [valid]. On x86 and x86_64 with SSE2 enabled and
+    /// `simd` feature on input is processed 16 bytes at a time, otherwise this is the same
+    /// as [valid]. Inputs of up to 8 bytes are always validated with the scalar
+    /// implementation since it is at least as fast for them.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use luhn3::decimal::valid_vec;
+    ///
+    /// // test Visa card is valid
+    /// assert!(valid_vec(b"4012888888881881"));
+    ///
+    /// // Microsoft's ISIN contains a valid checksum but it's
+    /// // not a decimal
+    /// assert!(!valid_vec(b"US5949181045"));
+    /// ```
+    pub fn valid_vec(ascii: &[u8]) -> bool {
+        match fold10v(false, ascii) {
+            Some(d) => d % 10 == 0,
+            None => false,
+        }
+    }
+
+    /// Validate a check digit with a vectorized implementation reporting non decimal input
+    ///
+    /// Returns `Ok` with the same result as [valid_vec] for decimal input and `Err` with the
+    /// index of the first non decimal byte otherwise.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use luhn3::decimal::check_vec;
+    ///
+    /// assert_eq!(Ok(true), check_vec(b"4012888888881881"));
+    /// assert_eq!(Ok(false), check_vec(b"4012888888881882"));
+    /// assert_eq!(Err(0), check_vec(b"US5949181045"));
+    /// ```
+    pub fn check_vec(ascii: &[u8]) -> Result<bool, usize> {
+        match fold10v(false, ascii) {
+            Some(d) => Ok(d.is_multiple_of(10) && !ascii.is_empty()),
+            None => find_digit(ascii, false).map_or(Ok(false), Err),
+        }
+    }
+
+    /// Validate a check digit with a vectorized implementation folding left to right
+    ///
+    /// Results are identical to those of [valid_vec]. With `simd` feature on x86 and x86_64
+    /// with SSE2 enabled parity of every digit is derived from the input length up front so
+    /// inputs longer than 16 bytes are read in order, 16 bytes at a time. Shorter inputs and
+    /// other targets use the same implementation as [valid_vec].
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use luhn3::decimal::valid_vec_forward;
+    ///
+    /// assert!(valid_vec_forward(b"4012888888881881"));
+    /// assert!(valid_vec_forward(b"4012888888881888883"));
+    /// assert!(!valid_vec_forward(b"US5949181045"));
+    /// ```
+    pub fn valid_vec_forward(ascii: &[u8]) -> bool {
+        match fold10v_forward(false, ascii) {
+            Some(d) => d.is_multiple_of(10) && !ascii.is_empty(),
+            None => false,
+        }
+    }
+
+    /// Try to compute a checksum for a sequence of ASCII bytes with a vectorized implementation
+    ///
+    /// Results are identical to those of [checksum], see [valid_vec] for details.
+    /// ```
+    /// use luhn3::decimal::checksum_vec;
+    ///
+    /// assert_eq!(Some(b'1'), checksum_vec(b"401288888888188"));
+    /// assert_eq!(None, checksum_vec(b"US594918104"));
+    /// ```
+    pub fn checksum_vec(ascii: &[u8]) -> Option<u8> {
+        let sum = fold10v(true, ascii)?;
+        Some(b'0' + ((10 - (sum % 10)) % 10) as u8)
+    }
+
+    /// Compute a check digit with a vectorized implementation along with its position
+    ///
+    /// Returns the check digit from [checksum_vec] and index at which it should be appended,
+    /// which is the length of the body.
+    /// ```
+    /// use luhn3::decimal::checksum_vec_at;
+    ///
+    /// assert_eq!(Some((b'1', 15)), checksum_vec_at(b"401288888888188"));
+    /// assert_eq!(None, checksum_vec_at(b"US594918104"));
+    /// ```
+    pub fn checksum_vec_at(ascii: &[u8]) -> Option<(u8, usize)> {
+        Some((checksum_vec(ascii)?, ascii.len()))
+    }
+
+    /// Validate up to 64 numbers at once
+    ///
+    /// Bit `i` of the result is set when `items[i]` passes [valid_vec]. Only the first 64
+    /// items are checked, the rest are ignored.
+    /// ```
+    /// use luhn3::decimal::valid_mask;
+    ///
+    /// let items: [&[u8]; 3] = [b"4111111111111111", b"4111111111111112", b"5555555555554444"];
+    /// assert_eq!(0b101, valid_mask(&items));
+    /// ```
+    pub fn valid_mask(items: &[&[u8]]) -> u64 {
+        items
+            .iter()
+            .take(64)
+            .enumerate()
+            .fold(0, |mask, (i, item)| mask | u64::from(valid_vec(item)) << i)
+    }
+
+    /// Append a check digit to a body and return it as a stack allocated string
+    ///
+    /// Returns `None` if body is not decimal or the result doesn't fit into `N` bytes.
+    /// Requires `heapless` feature.
+    /// ```
+    /// use luhn3::decimal::complete_heapless;
+    ///
+    /// let card = complete_heapless::<16>(b"401288888888188").unwrap();
+    /// assert_eq!("4012888888881881", card.as_str());
+    ///
+    /// // doesn't fit
+    /// assert_eq!(None, complete_heapless::<15>(b"401288888888188"));
+    /// ```
+    #[cfg(feature = "heapless")]
+    pub fn complete_heapless<const N: usize>(body: &[u8]) -> Option<heapless::String<N>> {
+        let check = checksum(body)?;
+        let mut res = heapless::String::new();
+        // checksum succeeded so body contains only ASCII digits
+        res.push_str(core::str::from_utf8(body).ok()?).ok()?;
+        res.push(char::from(check)).ok()?;
+        Some(res)
+    }
+
+    /// Append a check digit to a body in a caller provided buffer and return it as a string
+    ///
+    /// Result occupies the beginning of the buffer, the rest of it is left as is. Returns
+    /// `None` if body is not decimal or the result doesn't fit into `N` bytes.
+    /// ```
+    /// use luhn3::decimal::checksum_str_buf;
+    ///
+    /// let mut buf = [0; 16];
+    /// assert_eq!(Some("4012888888881881"), checksum_str_buf(b"401288888888188", &mut buf));
+    ///
+    /// // doesn't fit
+    /// let mut buf = [0; 15];
+    /// assert_eq!(None, checksum_str_buf(b"401288888888188", &mut buf));
+    /// ```
+    pub fn checksum_str_buf<'a, const N: usize>(
+        body: &[u8],
+        buf: &'a mut [u8; N],
+    ) -> Option<&'a str> {
+        let res = buf.get_mut(..body.len() + 1)?;
+        let check = checksum(body)?;
+        let (check_slot, body_slot) = res.split_last_mut()?;
+        body_slot.copy_from_slice(body);
+        *check_slot = check;
+        core::str::from_utf8(res).ok()
+    }
+
+    /// Write a masked copy of a decimal number with all but the last `reveal` digits hidden
+    ///
+    /// Hidden digits are replaced with `b'*'`, returns length of the masked number written to
+    /// the beginning of `out`. Check digit is not validated. Returns `None` if input is not
+    /// decimal or doesn't fit into `out`.
+    /// ```
+    /// use luhn3::decimal::mask;
+    ///
+    /// let mut out = [0; 19];
+    /// let len = mask(b"4111111111111111", &mut out, 4).unwrap();
+    /// assert_eq!(b"************1111", &out[..len]);
+    /// ```
+    pub fn mask(number: &[u8], out: &mut [u8], reveal: usize) -> Option<usize> {
+        let out = out.get_mut(..number.len())?;
+        if !number.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        let hidden = number.len().saturating_sub(reveal);
+        out[..hidden].fill(b'*');
+        out[hidden..].copy_from_slice(&number[hidden..]);
+        Some(number.len())
+    }
+
+    /// Format a decimal number for display inserting `sep` after every `group` digits
+    ///
+    /// Check digit is not validated. Returns `None` if input is not decimal or `group` is 0.
+    /// Requires `alloc` feature.
+    /// ```
+    /// use luhn3::decimal::format_grouped;
+    ///
+    /// let card = format_grouped(b"4111111111111111", 4, b' ').unwrap();
+    /// assert_eq!("4111 1111 1111 1111", card);
+    ///
+    /// // last group can be shorter
+    /// let card = format_grouped(b"378282246310005", 4, b'-').unwrap();
+    /// assert_eq!("3782-8224-6310-005", card);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn format_grouped(number: &[u8], group: usize, sep: u8) -> Option<alloc::string::String> {
+        if group == 0 || !number.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        let mut res = alloc::string::String::with_capacity(number.len() + number.len() / group);
+        for (i, chunk) in number.chunks(group).enumerate() {
+            if i > 0 {
+                res.push(char::from(sep));
+            }
+            // all the bytes are ASCII digits
+            res.push_str(core::str::from_utf8(chunk).ok()?);
+        }
+        Some(res)
+    }
+
+    /// Validate up to 64 numbers stored column wise
+    ///
+    /// `data` contains rows of `stride` bytes each, byte `r` of a row is a digit of number
+    /// `r`, the first row holds the left most digits. Only the first `count` bytes of each
+    /// row are checked so `count` must not exceed `stride`. Numbers are folded together one
+    /// row at a time which lets the compiler vectorize the loop across numbers.
+    ///
+    /// Bit `r` of the result is set when number `r` is valid. Returns `0` if `data` is not
+    /// made of whole rows or `count` exceeds `stride`, only the first 64 numbers are checked.
+    /// ```
+    /// use luhn3::decimal::valid_strided;
+    ///
+    /// // three numbers: 4111111111111111, 4012888888881881 and 4012888888881882
+    /// let data = b"444100111122188188188188188188188188111188188112";
+    /// assert_eq!(0b011, valid_strided(data, 3, 3));
+    /// ```
+    pub fn valid_strided(data: &[u8], count: usize, stride: usize) -> u64 {
+        let count = count.min(64);
+        if stride == 0 || count > stride || data.is_empty() || !data.len().is_multiple_of(stride) {
+            return 0;
+        }
+        let mut sums = [0u64; 64];
+        let mut bad = 0u64;
+        let mut double = false;
+        for row in data.rchunks_exact(stride) {
+            for (r, (sum, c)) in sums.iter_mut().zip(&row[..count]).enumerate() {
+                let digit = c.wrapping_sub(b'0');
+                bad |= u64::from(digit > 9) << r;
+                let digit = digit.min(9);
+                *sum += u64::from(if double {
+                    crate::tables::DOUBLE_LUT[digit as usize]
+                } else {
+                    digit
+                });
+            }
+            double = !double;
+        }
+        let valid = sums[..count]
+            .iter()
+            .enumerate()
+            .fold(0, |mask, (r, sum)| mask | u64::from(sum % 10 == 0) << r);
+        valid & !bad
+    }
+
+    /// Find all the valid numbers of `len` digits in a text
+    ///
+    /// Returns an iterator over offsets of such numbers, see [`Scan`] for details. Finding
+    /// 16 digit card numbers in a log file:
+    ///
+    /// ```
+    /// use luhn3::decimal::scan;
+    ///
+    /// let log = b"4012888888881881 ok\n4012888888881882 declined\n";
+    /// assert_eq!(scan(log, 16).collect::<Vec<_>>(), [0]);
+    /// ```
+    pub fn scan(text: &[u8], len: usize) -> Scan<'_> {
+        Scan::new(text, len, false)
+    }
+
+    /// Find all the valid numbers of `len` digits in a text, skipping non digits faster
+    ///
+    /// Same as [`scan`] but uses SSE2 when available to skip over runs of text without any
+    /// digits, 16 bytes at a time. Windows inside of runs of digits are still checked with
+    /// the same incremental sums as in [`scan`], see [`Scan`] for why. Offsets reported are
+    /// the same.
+    ///
+    /// ```
+    /// use luhn3::decimal::scan_vec;
+    ///
+    /// let text = b"lorem ipsum dolor sit amet 4012888888881881, consectetur adipiscing elit";
+    /// assert_eq!(scan_vec(text, 16).collect::<Vec<_>>(), [27]);
+    /// ```
+    pub fn scan_vec(text: &[u8], len: usize) -> Scan<'_> {
+        Scan::new(text, len, true)
+    }
+
+    /// Validate a large number of inputs in parallel
+    ///
+    /// Each item is checked with [valid_vec] on a `rayon` thread pool, results are in the
+    /// same order as inputs. Requires `rayon` feature.
+    /// ```
+    /// use luhn3::decimal::par_valid;
+    ///
+    /// let items: [&[u8]; 3] = [b"4111111111111111", b"4111111111111112", b"5555555555554444"];
+    /// assert_eq!(vec![true, false, true], par_valid(&items));
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_valid(items: &[&[u8]]) -> Vec<bool> {
+        use rayon::prelude::*;
+        items.par_iter().map(|item| valid_vec(item)).collect()
+    }
+
+    /// Compute two check digits for a sequence of ASCII bytes
+    ///
+    /// The first digit is the checksum of the body, the second one is the checksum of
+    /// the body followed by the first digit. Returns `None` if input is not decimal.
+    /// ```
+    /// use luhn3::decimal::{checksum2, valid};
+    ///
+    /// assert_eq!(Some([b'1', b'3']), checksum2(b"401288888888188"));
+    /// assert!(valid(b"4012888888881881"));
+    /// assert!(valid(b"40128888888818813"));
+    /// ```
+    pub fn checksum2(ascii: &[u8]) -> Option<[u8; 2]> {
+        let first = checksum(ascii)?;
+        // body shifts by one position after the first check digit is appended
+        let sum = fold10_swar(0x0201020102010201, 0x7f047f047f047f04, ascii)?
+            + u64::from(crate::tables::DOUBLE_LUT[(first - b'0') as usize]);
+        Some([first, b'0' + ((10 - (sum % 10)) % 10) as u8])
+    }
+
+    /// Fill a buffer with a valid number starting with a given prefix
+    ///
+    /// Prefix is copied to the front of the buffer, the rest except for the last byte is
+    /// filled with `b'0'` and the last byte is set to the check digit which is also
+    /// returned. Returns `None` leaving the buffer unchanged if prefix is not decimal or
+    /// there's no space left for the check digit.
+    /// ```
+    /// use luhn3::decimal::{fill_valid, valid};
+    ///
+    /// let mut card = [0; 16];
+    /// assert_eq!(Some(b'5'), fill_valid(&mut card, b"411111"));
+    /// assert_eq!(b"4111110000000005", &card);
+    /// assert!(valid(&card));
+    ///
+    /// // no space for the check digit
+    /// assert_eq!(None, fill_valid(&mut [0; 6], b"411111"));
+    /// ```
+    pub fn fill_valid(buf: &mut [u8], prefix: &[u8]) -> Option<u8> {
+        if prefix.len() >= buf.len() || !prefix.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        let (body, check) = buf.split_at_mut(buf.len() - 1);
+        body[..prefix.len()].copy_from_slice(prefix);
+        body[prefix.len()..].fill(b'0');
+        check[0] = checksum(body)?;
+        Some(check[0])
+    }
+
+    /// Fill a buffer with a random valid number using a caller provided source of entropy
+    ///
+    /// Every byte of the buffer except for the last one gets a random digit, bytes from
+    /// `rng` above 249 are discarded so digits are uniformly distributed as long as `rng`
+    /// bytes are. Last byte is then set to the check digit. Returns `false` and leaves
+    /// `buf` unchanged if it is shorter than 2 bytes.
+    /// ```
+    /// use luhn3::decimal::{random_valid_with, valid};
+    ///
+    /// // xorshift, use a hardware RNG or similar in real code
+    /// let mut state = 0x2545f491u32;
+    /// let rng = || {
+    ///     state ^= state << 13;
+    ///     state ^= state >> 17;
+    ///     state ^= state << 5;
+    ///     state as u8
+    /// };
+    /// let mut buf = [0; 16];
+    /// assert!(random_valid_with(rng, &mut buf));
+    /// assert!(valid(&buf));
+    /// ```
+    pub fn random_valid_with<F: FnMut() -> u8>(mut rng: F, buf: &mut [u8]) -> bool {
+        if buf.len() < 2 {
+            return false;
+        }
+        let len = buf.len();
+        for c in &mut buf[..len - 1] {
+            *c = loop {
+                let b = rng();
+                if b < 250 {
+                    break b'0' + b % 10;
+                }
+            };
+        }
+        set_check_digit(buf)
+    }
+
+    /// Recompute a check digit for a number that already has a check digit slot
+    ///
+    /// Last byte of the input is ignored completely so it can contain an old check digit or
+    /// a placeholder such as `X`, the rest is treated the same way as input to [checksum].
+    /// Returns `None` for empty input.
+    /// ```
+    /// use luhn3::decimal::recompute;
+    ///
+    /// // Check digit is recomputed from the body
+    /// assert_eq!(Some(b'1'), recompute(b"401288888888188X"));
+    ///
+    /// // Old check digit doesn't matter
+    /// assert_eq!(Some(b'1'), recompute(b"4012888888881880"));
+    ///
+    /// // There's no check digit slot
+    /// assert_eq!(None, recompute(b""));
+    /// ```
+    pub fn recompute(number: &[u8]) -> Option<u8> {
+        let (_, body) = number.split_last()?;
+        checksum(body)
+    }
+
+    /// Write a check digit into the last byte of a number
+    ///
+    /// Same as [`recompute`] but writes the result in place, returns `false` and leaves the
+    /// input unchanged if it is empty or the body is not decimal.
+    /// ```
+    /// use luhn3::decimal::{set_check_digit, valid};
+    ///
+    /// let mut number = *b"401288888888188_";
+    /// assert!(set_check_digit(&mut number));
+    /// assert_eq!(b"4012888888881881", &number);
+    /// assert!(valid(&number));
+    ///
+    /// assert!(!set_check_digit(&mut []));
+    /// ```
+    pub fn set_check_digit(number: &mut [u8]) -> bool {
+        let Some((slot, body)) = number.split_last_mut() else {
+            return false;
+        };
+        match checksum(body) {
+            Some(check) => {
+                *slot = check;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Recover a single unknown digit marked with `b'?'` in a number with a check digit
+    ///
+    /// Doubling is a permutation of digits so there's always exactly one digit that makes
+    /// the number valid, regardless of the unknown position. Returns `None` unless there's
+    /// exactly one `b'?'` and the rest of the input is decimal digits.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use luhn3::decimal::solve_wildcard;
+    ///
+    /// assert_eq!(Some(b'8'), solve_wildcard(b"40128?8888881881"));
+    ///
+    /// // the unknown can be the check digit itself
+    /// assert_eq!(Some(b'1'), solve_wildcard(b"401288888888188?"));
+    ///
+    /// // more than one unknown digit
+    /// assert_eq!(None, solve_wildcard(b"40128?888888188?"));
+    /// ```
+    pub fn solve_wildcard(number: &[u8]) -> Option<u8> {
+        solve_placeholder(number, b'?').map(|(_, digit)| digit)
+    }
+
+    /// Replace a single `b'*'` placeholder in a template with a digit that makes it valid
+    ///
+    /// Placeholder usually marks the check digit position but can be anywhere in the
+    /// template, see [`solve_wildcard`]. Returns the digit written or `None` and leaves
+    /// the template unchanged unless there's exactly one `b'*'` and the rest are digits.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use luhn3::decimal::{fill_placeholder, valid};
+    ///
+    /// let mut number = *b"401288888888188*";
+    /// assert_eq!(Some(b'1'), fill_placeholder(&mut number));
+    /// assert_eq!(b"4012888888881881", &number);
+    /// assert!(valid(&number));
+    ///
+    /// // nothing to fill
+    /// assert_eq!(None, fill_placeholder(&mut number));
+    /// ```
+    pub fn fill_placeholder(template: &mut [u8]) -> Option<u8> {
+        let (pos, digit) = solve_placeholder(template, b'*')?;
+        template[pos] = digit;
+        Some(digit)
+    }
+
+    /// Find the only `placeholder` byte and a digit to replace it with to get a valid number
+    fn solve_placeholder(number: &[u8], placeholder: u8) -> Option<(usize, u8)> {
+        let pos = number.iter().rposition(|&c| c == placeholder)?;
+        if number[..pos].contains(&placeholder) {
+            return None;
+        }
+        let digits = number
+            .iter()
+            .rev()
+            .map(|&c| if c == placeholder { b'0' } else { c });
+        let sum = fold10_ascii(false, digits)?;
+        let missing = ((10 - sum % 10) % 10) as u8;
+        // odd positions from the right are doubled
+        let digit = if (number.len() - pos).is_multiple_of(2) {
+            tables::DOUBLE_LUT.iter().position(|&d| d == missing)? as u8
+        } else {
+            missing
+        };
+        Some((pos, b'0' + digit))
+    }
+}
+
+pub mod alphanum {
+    //! # Operations on alphanumeric Luhn checksums
+    //!
+    //! This version of the algorithm can operate on decimal digits and capital ASCII letters.
+    //!
+    //! # Usage
+    //!
+    //! ```
+    //! // Microsoft's ISIN is valid
+    //! assert!(luhn3::alphanum::valid(b"US5949181045"));
+    //! ```
+    //!
+    //! See also [decimal][crate::decimal]
+    use crate::*;
+
+    /// Validate a check digit using Luhn algorithm
+    ///
+    /// ```
+    /// use luhn3::alphanum::valid;
+    ///
+    /// // Microsoft's ISIN is valid
+    /// assert!(valid(b"US5949181045"));
+    ///
+    /// // Not a valid sequence
+    /// let banana = String::from("banana");
+    /// assert!(!valid(banana.as_bytes()));
+    ///
+    /// // Even less valid sequence
+    /// let noms = "口水鸡";
+    /// assert!(!valid(noms.as_bytes()));
+    /// ```
+    pub fn valid(ascii: &[u8]) -> bool {
+        match fold36(false, ascii) {
+            Some(v) => v % 10 == 0,
+            None => false,
+        }
+    }
+
+    /// Validate a check digit using Luhn algorithm
+    ///
+    /// Results are identical to those of [valid] but compiler can optimize this version better
+    /// ```
+    /// use luhn3::alphanum::valid;
+    ///
+    /// // Microsoft's ISIN is valid
+    /// assert!(valid(b"US5949181045"));
+    ///
+    /// // Not a valid sequence
+    /// let banana = String::from("banana");
+    /// assert!(!valid(banana.as_bytes()));
+    ///
+    /// // Even less valid sequence
+    /// let noms = "口水鸡";
+    /// assert!(!valid(noms.as_bytes()));
+    /// ```
+    pub fn valid_arr<const T: usize>(ascii: &[u8; T]) -> bool {
+        match fold36(false, ascii) {
+            Some(v) => v % 10 == 0,
+            None => false,
+        }
+    }
+
+    /// Validate a check digit using Luhn algorithm with a vectorized input check
+    ///
+    /// Results are identical to those of [valid]. On x86 and x86_64 with SSE2 enabled and
+    /// `simd` feature on input is checked for invalid bytes 16 bytes at a time before
+    /// computing the checksum which makes rejecting malformed input faster, otherwise the
+    /// check is scalar.
+    /// ```
+    /// use luhn3::alphanum::valid_vec;
+    ///
+    /// // Microsoft's ISIN is valid
+    /// assert!(valid_vec(b"US5949181045"));
+    ///
+    /// // Lower case letters are not accepted
+    /// assert!(!valid_vec(b"us5949181045"));
+    /// ```
+    pub fn valid_vec(ascii: &[u8]) -> bool {
+        all_alphanum(ascii) && valid(ascii)
+    }
+
+    /// Validate a check digit using Luhn algorithm and report the reason for failure
+    ///
+    /// Same as [valid] but returns a [`LuhnError`] describing what is wrong with the input,
+    /// [`LuhnError::InvalidByte`] contains index of the first byte that is not a decimal
+    /// digit or a capital letter. With `tracing` or `defmt` feature enabled failures are
+    /// reported as `debug` events.
+    /// ```
+    /// use luhn3::{alphanum::check, LuhnError};
+    ///
+    /// assert_eq!(Ok(()), check(b"US5949181045"));
+    /// assert_eq!(Err(LuhnError::InvalidChecksum), check(b"US5949181046"));
+    /// assert_eq!(Err(LuhnError::InvalidByte(1)), check(b"Us5949181045"));
+    /// assert_eq!(Err(LuhnError::Empty), check(b""));
+    /// ```
+    pub fn check(ascii: &[u8]) -> Result<(), LuhnError> {
+        let res = match fold36(false, ascii) {
+            _ if ascii.is_empty() => Err(LuhnError::Empty),
+            Some(v) if v % 10 == 0 => Ok(()),
+            Some(_) => Err(LuhnError::InvalidChecksum),
+            None => Err(LuhnError::InvalidByte(
+                ascii
+                    .iter()
+                    .position(|c| !(c.is_ascii_digit() || c.is_ascii_uppercase()))
+                    .unwrap_or(0),
+            )),
+        };
+        crate::error::report(res)
+    }
+
+    /// Validate a check digit using Luhn algorithm and check the input length
+    ///
+    /// Same as [valid] but inputs shorter than `min_len` or longer than `max_len`
+    /// are rejected.
+    ///
+    /// # Panics
+    /// Function contains [debug_assert] to ensure `min_len <= max_len`
+    ///
+    /// ```
+    /// use luhn3::alphanum::{valid, valid_strict};
+    ///
+    /// // ISIN codes are always 12 symbols long
+    /// assert!(valid_strict(b"US5949181045", 12, 12));
+    ///
+    /// // A single zero contains a valid checksum
+    /// assert!(valid(b"0"));
+    /// // but it's too short to be useful
+    /// assert!(!valid_strict(b"0", 2, 12));
+    /// ```
+    pub fn valid_strict(ascii: &[u8], min_len: usize, max_len: usize) -> bool {
+        debug_assert!(min_len <= max_len);
+        (min_len..=max_len).contains(&ascii.len()) && valid(ascii)
+    }
+
+    /// Validate a check digit using Luhn algorithm on user typed input
+    ///
+    /// Lower case letters are treated as upper case ones, ASCII spaces and dashes are skipped
+    /// wherever they are, including between letters. Anything else makes input invalid as
+    /// does input without any symbols.
+    /// ```
+    /// use luhn3::alphanum::valid_relaxed;
+    ///
+    /// assert!(valid_relaxed(b"us 5949 1810 45"));
+    /// assert!(valid_relaxed(b"U-S5949181045"));
+    ///
+    /// // periods are not separators
+    /// assert!(!valid_relaxed(b"US.5949181045"));
+    /// assert!(!valid_relaxed(b" - "));
+    /// ```
+    pub fn valid_relaxed(input: &[u8]) -> bool {
+        let mut mixer = Mixer::default();
+        for &c in input {
+            match c {
+                b'0'..=b'9' => mixer.push(c - b'0'),
+                b'A'..=b'Z' | b'a'..=b'z' => mixer.push_letter(c.to_ascii_uppercase()),
+                b' ' | b'-' => {}
+                _ => return false,
+            }
+        }
+        mixer.valid()
+    }
+
+    /// Write contribution of each expanded digit of a code with a check digit to the Luhn sum
+    ///
+    /// Letters expand into two digits so `out` gets a value for each of them, for example
+    /// `"A"` stands for `10` and gets two values. Returns the number of values written,
+    /// `None` leaving `out` unchanged if input contains anything other than decimal digits
+    /// and capital letters or `out` is too short.
+    /// ```
+    /// use luhn3::alphanum::weighted;
+    ///
+    /// let mut out = [0; 8];
+    /// assert_eq!(Some(4), weighted(b"U42", &mut out));
+    /// // 'U' is 30, 3 and 4 are doubled
+    /// assert_eq!([6, 0, 8, 2], out[..4]);
+    /// assert_eq!(None, weighted(b"US5949181045", &mut out));
+    /// ```
+    pub fn weighted(input: &[u8], out: &mut [u8]) -> Option<usize> {
+        use crate::tables::DOUBLE_LUT;
+        let mut len = 0;
+        for &c in input {
+            match c {
+                b'0'..=b'9' => len += 1,
+                b'A'..=b'Z' => len += 2,
+                _ => return None,
+            }
+        }
+        let out = out.get_mut(..len)?;
+        // filled right to left, right most digit is not doubled
+        let mut pos = len;
+        let mut double = false;
+        for &c in input.iter().rev() {
+            let (tens, ones) = match c {
+                b'0'..=b'9' => (None, c - b'0'),
+                _ => {
+                    let value = c - b'A' + 10;
+                    (Some(value / 10), value % 10)
+                }
+            };
+            for digit in core::iter::once(ones).chain(tens) {
+                pos -= 1;
+                out[pos] = if double {
+                    DOUBLE_LUT[digit as usize]
+                } else {
+                    digit
+                };
+                double = !double;
+            }
+        }
+        Some(len)
+    }
+
+    /// Validate a check digit using Luhn algorithm on bytes read from a stream
+    ///
+    /// Input is folded with a [`Mixer`] as it is read so the whole stream doesn't need to be
+    /// kept in memory, results are identical to those of [valid] on the whole input. Reading
+    /// stops at the first byte that is not a decimal digit or a capital letter. Requires
+    /// `std` feature.
+    /// ```
+    /// use luhn3::alphanum::valid_reader;
+    /// use std::io::Cursor;
+    ///
+    /// assert!(valid_reader(Cursor::new(b"US5949181045")).unwrap());
+    /// assert!(!valid_reader(Cursor::new(b"US5949181046")).unwrap());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn valid_reader<R: std::io::Read>(mut reader: R) -> std::io::Result<bool> {
+        let mut mixer = Mixer::default();
+        let mut buf = [0; 1024];
+        loop {
+            let n = match reader.read(&mut buf) {
+                Ok(0) => return Ok(mixer.valid()),
+                Ok(n) => n,
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+            for &c in &buf[..n] {
+                match c {
+                    b'0'..=b'9' => mixer.push(c - b'0'),
+                    b'A'..=b'Z' => mixer.push_letter(c),
+                    _ => return Ok(false),
+                }
+            }
+        }
+    }
+
+    /// Try to compute a check digit for a sequence of ASCII bytes
+    ///
+    /// If input contains only bytes in `b'0'..b'9' | b'A'..b'Z'` range output
+    /// is guaranteed to be a byte in `b'0'..=b'9'` range or None otherwise.
+    /// ```
+    /// use luhn3::alphanum::checksum;
+    ///
+    /// // Can calculate a checksum for a string with decimal digits
+    /// assert_eq!(Some(b'1'), checksum(b"401288888888188"));
+    ///
+    /// // Can calculate a checksum for Microsoft's ISIN
+    /// assert_eq!(Some(b'5'), checksum(b"US594918104"));
+    ///
+    /// // Not a valid sequence
+    /// let banana = String::from("banana");
+    /// assert_eq!(None, checksum(banana.as_bytes()));
+    ///
+    /// // Even less valid sequence
+    /// let noms = "口水鸡";
+    /// assert_eq!(None, checksum(noms.as_bytes()));
+    /// ```
+    pub fn checksum(ascii: &[u8]) -> Option<u8> {
+        let sum = fold36(true, ascii)?;
+        Some(b'0' + ((10 - (sum % 10)) % 10) as u8)
+    }
+
+    /// Compute a check digit using Luhn algorithm, vectorized
+    ///
+    /// Results are identical to those of [checksum]. On x86 and x86_64 with SSE2 enabled and
+    /// `simd` feature on input is folded 16 bytes at a time including letters, otherwise
+    /// this is the same as [checksum].
+    /// ```
+    /// use luhn3::alphanum::checksum_vec;
+    ///
+    /// assert_eq!(Some(b'5'), checksum_vec(b"US594918104"));
+    /// assert_eq!(Some(b'1'), checksum_vec(b"401288888888188"));
+    /// assert_eq!(None, checksum_vec(b"us594918104"));
+    /// ```
+    pub fn checksum_vec(ascii: &[u8]) -> Option<u8> {
+        let sum = fold36v(true, ascii)?;
+        Some(b'0' + ((10 - (sum % 10)) % 10) as u8)
+    }
+
+    /// Append a check digit to a body and return the complete number
+    ///
+    /// Returns `None` if body contains anything other than decimal digits and capital
+    /// letters. Requires `alloc` feature.
+    /// ```
+    /// use luhn3::alphanum::complete;
+    ///
+    /// assert_eq!(Some("US5949181045".to_string()), complete(b"US594918104"));
+    /// assert_eq!(None, complete(b"us594918104"));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn complete(body: &[u8]) -> Option<alloc::string::String> {
+        let check = checksum(body)?;
+        let mut res = alloc::string::String::with_capacity(body.len() + 1);
+        // checksum succeeded so body contains only ASCII digits and letters
+        res.push_str(core::str::from_utf8(body).ok()?);
+        res.push(char::from(check));
+        Some(res)
+    }
+
+    /// Append a check digit to a body of any case writing the result into `out`
+    ///
+    /// Letters are written in upper case when `uppercase` is set and in lower case
+    /// otherwise, check digit is computed the same way as [checksum] does for the upper case
+    /// body. Returns the length written, `None` leaving `out` unchanged if body contains
+    /// anything other than decimal digits and ASCII letters or `out` is too short.
+    /// ```
+    /// use luhn3::alphanum::complete_into;
+    ///
+    /// let mut out = [0; 12];
+    /// assert_eq!(Some(12), complete_into(b"us594918104", &mut out, true));
+    /// assert_eq!(b"US5949181045", &out);
+    /// assert_eq!(Some(12), complete_into(b"US594918104", &mut out, false));
+    /// assert_eq!(b"us5949181045", &out);
+    ///
+    /// assert_eq!(None, complete_into(b"US5949181045", &mut out, true));
+    /// ```
+    pub fn complete_into(body: &[u8], out: &mut [u8], uppercase: bool) -> Option<usize> {
+        let out = out.get_mut(..body.len() + 1)?;
+        let mut mixer = Mixer::default();
+        for &c in body {
+            match c {
+                b'0'..=b'9' => mixer.push(c - b'0'),
+                b'A'..=b'Z' | b'a'..=b'z' => mixer.push_letter(c.to_ascii_uppercase()),
+                _ => return None,
+            }
+        }
+        let (check, out_body) = out.split_last_mut()?;
+        for (o, c) in out_body.iter_mut().zip(body) {
+            *o = if uppercase {
+                c.to_ascii_uppercase()
+            } else {
+                c.to_ascii_lowercase()
+            };
+        }
+        *check = mixer.checksum();
+        Some(out.len())
+    }
+}
+
+pub mod aadhaar;
+#[cfg(feature = "capi")]
+pub mod capi;
+pub mod card;
+pub mod gs1;
+pub mod iccid;
+pub mod mod11;
+pub mod modn;
+pub mod npi;
+pub mod simd;
+pub mod sin;
+pub mod verhoeff;
+pub mod visa;
+
+/// Fail compilation unless a string literal is a decimal number with a valid check digit
+///
+/// Meant for card numbers and product codes embedded in the source, check is evaluated
+/// at compile time with [`decimal::valid_const`] so there's no runtime cost. Requires Rust
+/// 1.87 or newer, same as the rest of the crate: [`decimal::valid_const`] uses
+/// `u64::is_multiple_of` which is only `const` starting from that version.
+///
+/// ```rust
+/// luhn3::assert_valid!("4012888888881881");
+/// ```
+///
+/// A typo is caught by the compiler
+/// ```compile_fail
+/// luhn3::assert_valid!("4012888888881882");
+/// ```
+///
+/// Only decimal digits are accepted
+/// ```compile_fail
+/// luhn3::assert_valid!("US5949181045");
+/// ```
+#[macro_export]
+macro_rules! assert_valid {
+    ($number:expr) => {
+        const _: () = assert!(
+            $crate::decimal::valid_const($number.as_bytes()),
+            "not a valid Luhn number"
+        );
+    };
+}
+
+#[cfg(not(feature = "default-decimal"))]
+pub use crate::alphanum::*;
+#[cfg(feature = "default-decimal")]
+pub use crate::decimal::*;
+
+/// Compute a check digit picking the fastest implementation for the input
+///
+/// Input is folded as decimal first, same as in [`valid_fast`], if it turns out to contain
+/// anything other than digits the check digit is computed with [`alphanum::checksum`].
+/// Results are identical to those of [`alphanum::checksum`] but decimal only input is
+/// handled at the speed of [`decimal::checksum_vec`].
+/// ```
+/// use luhn3::checksum_auto;
+///
+/// assert_eq!(Some(b'1'), checksum_auto(b"401288888888188"));
+/// assert_eq!(Some(b'5'), checksum_auto(b"US594918104"));
+/// assert_eq!(None, checksum_auto(b"banana"));
+/// ```
+pub fn checksum_auto(ascii: &[u8]) -> Option<u8> {
+    match fold10v(true, ascii) {
+        Some(sum) => Some(b'0' + ((10 - (sum % 10)) % 10) as u8),
+        None => alphanum::checksum(ascii),
+    }
+}
+
+/// Validate a check digit picking the fastest implementation for the input
+///
+/// Input is folded as decimal first, if it turns out to contain anything other than digits
+/// it is validated with [`alphanum::valid`]. Results are identical to those of
+/// [`alphanum::valid`] but decimal only input is validated at the speed of
+/// [`decimal::valid_vec`].
+/// ```
+/// use luhn3::valid_fast;
+///
+/// assert!(valid_fast(b"4012888888881881"));
+/// assert!(valid_fast(b"US5949181045"));
+/// assert!(!valid_fast(b"banana"));
+/// assert!(!valid_fast(b""));
+/// ```
+pub fn valid_fast(ascii: &[u8]) -> bool {
+    match fold10v(false, ascii) {
+        Some(sum) => sum.is_multiple_of(10) && !ascii.is_empty(),
+        None => alphanum::valid(ascii),
+    }
+}
+
+/// Kind of a number with a valid check digit, see [`classify`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputKind {
+    /// Decimal digits only with a valid check digit, such as a card number
+    Decimal,
+    /// Decimal digits and capital letters with a valid check digit, such as an ISIN
+    Alphanumeric,
+    /// Empty, contains other bytes or the check digit is wrong
+    Invalid,
+}
+
+/// Tell if input is a valid decimal or alphanumeric number
+///
+/// Input consisting of digits only is validated as decimal, anything else as
+/// alphanumeric. Same as [`valid_fast`] but reports which of the two validations passed.
+/// ```
+/// use luhn3::{classify, InputKind};
+///
+/// assert_eq!(InputKind::Decimal, classify(b"4012888888881881"));
+/// assert_eq!(InputKind::Alphanumeric, classify(b"US5949181045"));
+/// assert_eq!(InputKind::Invalid, classify(b"banana"));
+/// assert_eq!(InputKind::Invalid, classify(b"4012888888881882"));
+/// ```
+pub fn classify(ascii: &[u8]) -> InputKind {
+    match fold10v(false, ascii) {
+        Some(sum) if sum.is_multiple_of(10) && !ascii.is_empty() => InputKind::Decimal,
+        Some(_) => InputKind::Invalid,
+        None if alphanum::valid(ascii) => InputKind::Alphanumeric,
+        None => InputKind::Invalid,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    const DECIMAL_LUHN_SAMPLES: &[&str] = &[
+        // test cc numbers
+        "378282246310005",     // American Express
+        "371449635398431",     // American Express
+        "378734493671000",     // American Express Corporate
+        "5610591081018250",    // Australian BankCard
+        "30569309025904",      // Diners Club
+        "38520000023237",      // Diners Club
+        "6011111111111117",    // Discover
+        "6011000990139424",    // Discover
+        "3530111333300000",    // JCB
+        "3566002020360505",    // JCB
+        "5555555555554444",    // MasterCard
+        "5105105105105100",    // MasterCard
+        "4111111111111111",    // Visa
+        "4012888888881881",    // Visa
+        "4012888888881888883", // Visa, 19 digits
+        "4222222222222",       // Visa
+        "5019717010103742",    // Dankort (PBS)
+        "6331101999990016",    // Switch/Solo (Paymentech)
+        "6205500000000000004", // UnionPay, 19 digits
+        // random IMEI
+        "358771054102508", // Apple iPad Air (A1475)
+        "867103029110602", // HUAWEI G610-U20
         "358625057927511", // Cinterion PHS8-P
         "359513063006075", // Samsung GT-I9300I
         "351813076684290", // Samsung SM-G531H/DS
     ];
 
-    fn change_digit(digit: u8) -> u8 {
-        if digit == b'9' {
-            b'0'
-        } else {
-            digit + 1
+    fn change_digit(digit: u8) -> u8 {
+        if digit == b'9' {
+            b'0'
+        } else {
+            digit + 1
+        }
+    }
+
+    /// Pseudo random numbers for generated inputs without extra dependencies
+    fn xorshift(seed: &mut u64) -> u64 {
+        *seed ^= *seed << 13;
+        *seed ^= *seed >> 7;
+        *seed ^= *seed << 17;
+        *seed
+    }
+
+    #[test]
+    fn test_tables_from_first_principles() {
+        use crate::tables::*;
+        // doubled digit replaced with a sum of its digits
+        let double = |d: u8| (d * 2) / 10 + (d * 2) % 10;
+        for digit in 0..10 {
+            assert_eq!(double(digit), DOUBLE_LUT[digit as usize]);
+        }
+        // letters stand for two digit numbers starting with 'A' = 10
+        for letter in 0..26 {
+            let (tens, ones) = ((letter + 10) / 10, (letter + 10) % 10);
+            assert_eq!(tens + double(ones), LETTER_LUT_T[letter as usize]);
+            assert_eq!(double(tens) + ones, LETTER_LUT_F[letter as usize]);
+            // same with or without `compact` feature
+            assert_eq!(
+                LETTER_LUT_T[letter as usize],
+                crate::letter_contribution(letter, true)
+            );
+            assert_eq!(
+                LETTER_LUT_F[letter as usize],
+                crate::letter_contribution(letter, false)
+            );
+        }
+    }
+
+    #[test]
+    fn test_empty_input() {
+        let m = crate::Mixer::default();
+        assert!(m.is_empty());
+        assert_eq!(0, m.len());
+        assert!(!m.valid());
+        assert_eq!(b'0', m.checksum());
+
+        // kept as is for compatibility
+        assert!(crate::decimal::valid(b""));
+        assert!(crate::decimal::valid_arr(b""));
+        assert!(crate::decimal::valid_vec(b""));
+        assert!(crate::alphanum::valid(b""));
+        assert!(crate::alphanum::valid_arr(b""));
+        assert_eq!(Some(b'0'), crate::decimal::checksum(b""));
+        assert_eq!(Some(b'0'), crate::alphanum::checksum(b""));
+    }
+
+    #[test]
+    fn test_mixer_len() {
+        let mut m = crate::Mixer::default();
+        for (i, c) in b"378282246310005".iter().enumerate() {
+            assert_eq!(i, m.len());
+            m.push(c - b'0');
+            assert!(!m.is_empty());
+        }
+        assert_eq!(15, m.len());
+        m.push_letter(b'Z');
+        assert_eq!(17, m.len());
+    }
+
+    #[test]
+    fn test_mixer_checksum_parity() {
+        for sample in DECIMAL_LUHN_SAMPLES.iter().map(|s| s.as_bytes()) {
+            for len in 1..=10 {
+                let body = &sample[..len];
+                let mut m = crate::Mixer::default();
+                for c in body {
+                    m.push(c - b'0');
+                }
+                assert_eq!(crate::decimal::checksum(body), Some(m.checksum()));
+                m.push(m.checksum() - b'0');
+                assert!(m.valid());
+            }
+        }
+    }
+
+    #[test]
+    fn test_long_input() {
+        let mut s = vec![b'9'; 1_000_000];
+        s.push(crate::decimal::checksum(&s).unwrap());
+        assert!(crate::decimal::valid(&s));
+        assert!(crate::decimal::valid_vec(&s));
+        assert!(crate::alphanum::valid(&s));
+
+        let mut m = crate::Mixer::default();
+        for c in &s {
+            m.push(c - b'0');
+        }
+        assert!(m.valid());
+        assert_eq!(s.len(), m.len());
+    }
+
+    #[cfg(feature = "defmt")]
+    #[test]
+    fn test_defmt_format_all_variants() {
+        use crate::LuhnError;
+        fn format<T: defmt::Format>(_: T) {}
+        for err in [
+            LuhnError::Empty,
+            LuhnError::InvalidByte(0),
+            LuhnError::InvalidChecksum,
+            LuhnError::InvalidLength,
+        ] {
+            format(err);
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_tracing_reports_failures() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata, Subscriber};
+
+        struct Counter(Arc<AtomicUsize>);
+        impl Subscriber for Counter {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, _: &Attributes<'_>) -> Id {
+                Id::from_u64(1)
+            }
+            fn record(&self, _: &Id, _: &Record<'_>) {}
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, _: &Event<'_>) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+            fn enter(&self, _: &Id) {}
+            fn exit(&self, _: &Id) {}
+        }
+
+        let events = Arc::new(AtomicUsize::new(0));
+        tracing::subscriber::with_default(Counter(events.clone()), || {
+            assert!(crate::decimal::check(b"4012888888881881").is_ok());
+            assert_eq!(0, events.load(Ordering::SeqCst));
+            assert!(crate::decimal::check(b"4012888888881882").is_err());
+            assert!(crate::alphanum::check(b"banana").is_err());
+        });
+        assert_eq!(2, events.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_ae_checksum() {
+        let (&check, body) = b"378282246310005".split_last().unwrap();
+        assert_eq!(Some(check), crate::decimal::checksum(body));
+    }
+
+    #[test]
+    fn test_valid_arr_fixed_array() {
+        let card: [u8; 16] = [
+            b'4', b'0', b'1', b'2', b'8', b'8', b'8', b'8', b'8', b'8', b'8', b'8', b'1', b'8',
+            b'8', b'1',
+        ];
+        assert!(crate::decimal::valid_arr(&card));
+        assert_eq!(
+            crate::decimal::valid(&card),
+            crate::decimal::valid_arr(&card)
+        );
+
+        let mut card = card;
+        card[3] = change_digit(card[3]);
+        assert!(!crate::decimal::valid_arr(&card));
+    }
+
+    #[test]
+    fn test_decimal_luhn_checksum() {
+        for sample in DECIMAL_LUHN_SAMPLES {
+            // number is valid as is valid
+            assert!(crate::decimal::valid(sample.as_bytes()));
+
+            // luhn checksum detects a single changed digit
+            let mut s = Vec::from(*sample);
+            s[3] = change_digit(s[3]);
+            assert!(!crate::decimal::valid(&s));
+
+            // luhn checksum also detects two digit swap
+            let mut s = Vec::from(*sample);
+            if s[3] != s[4] {
+                s.swap(3, 4);
+                assert!(!crate::decimal::valid(&s));
+            }
+
+            // last digit is it's luhn checksum
+            let (checksum, body) = sample.as_bytes().split_last().unwrap();
+            assert_eq!(Some(*checksum), crate::decimal::checksum(body));
+            assert_eq!(
+                Some(*checksum),
+                crate::decimal::recompute(sample.as_bytes())
+            );
+
+            // and finally only decimal numbers are accepted
+            let mut s = Vec::from(*sample);
+            s[3] = b'x';
+            assert!(!crate::decimal::valid(&s));
+        }
+    }
+
+    #[test]
+    fn test_decimal_vec_matches_scalar() {
+        for sample in DECIMAL_LUHN_SAMPLES {
+            let sample = sample.as_bytes();
+            assert!(crate::decimal::valid_vec(sample));
+            for i in 0..sample.len() {
+                let mut s = Vec::from(sample);
+                s[i] = change_digit(s[i]);
+                assert_eq!(crate::decimal::valid(&s), crate::decimal::valid_vec(&s));
+                s[i] = b'x';
+                assert!(!crate::decimal::valid_vec(&s));
+            }
+            for len in 0..sample.len() {
+                let body = &sample[..len];
+                assert_eq!(
+                    crate::decimal::checksum(body),
+                    crate::decimal::checksum_vec(body)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_decimal_vec_parity_matches_scalar() {
+        for len in 0..40 {
+            for seed in 0..10u8 {
+                let mut s = (0..len)
+                    .map(|i| b'0' + ((i as u8).wrapping_mul(7).wrapping_add(seed) % 10))
+                    .collect::<Vec<_>>();
+                let check = crate::decimal::checksum(&s);
+                assert_eq!(check, crate::decimal::checksum_vec(&s), "{:?}", s);
+                assert_eq!(
+                    Some((check.unwrap(), len)),
+                    crate::decimal::checksum_vec_at(&s)
+                );
+                s.push(check.unwrap());
+                assert!(crate::decimal::valid(&s));
+                assert!(crate::decimal::valid_vec(&s));
+            }
+        }
+    }
+
+    #[test]
+    fn test_decimal_digits_match_ascii() {
+        for sample in DECIMAL_LUHN_SAMPLES {
+            let sample = sample.as_bytes();
+            for i in 0..sample.len() {
+                let mut s = Vec::from(sample);
+                s[i] = change_digit(s[i]);
+                let digits = s.iter().map(|c| c - b'0').collect::<Vec<_>>();
+                assert_eq!(
+                    crate::decimal::valid(&s),
+                    crate::decimal::valid_digits(&digits)
+                );
+                assert_eq!(
+                    crate::decimal::checksum(&s[..i]),
+                    Some(b'0' + crate::decimal::checksum_digits(&digits[..i]))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_decimal_valid_mask() {
+        let mut items = Vec::new();
+        for sample in DECIMAL_LUHN_SAMPLES.iter().cycle().take(70) {
+            let mut s = Vec::from(*sample);
+            if items.len() % 3 == 0 {
+                s[3] = change_digit(s[3]);
+            }
+            items.push(s);
+        }
+        let items = items.iter().map(|s| s.as_slice()).collect::<Vec<_>>();
+        let mask = crate::decimal::valid_mask(&items);
+        for (i, item) in items.iter().take(64).enumerate() {
+            assert_eq!(crate::decimal::valid(item), mask & (1 << i) != 0);
+        }
+        assert_eq!(mask, crate::decimal::valid_mask(&items[..64]));
+    }
+
+    #[test]
+    fn test_decimal_edit_distance() {
+        for sample in DECIMAL_LUHN_SAMPLES {
+            let sample = sample.as_bytes();
+            assert_eq!(Some(0), crate::decimal::edit_distance_to_valid(sample));
+            for i in 0..sample.len() {
+                let mut s = Vec::from(sample);
+                s[i] = change_digit(s[i]);
+                assert_eq!(Some(1), crate::decimal::edit_distance_to_valid(&s));
+            }
+        }
+        assert_eq!(None, crate::decimal::edit_distance_to_valid(b""));
+    }
+
+    #[test]
+    fn test_decimal_check() {
+        for sample in DECIMAL_LUHN_SAMPLES {
+            assert_eq!(Ok(()), crate::decimal::check(sample.as_bytes()));
+            let mut s = Vec::from(*sample);
+            s[3] = change_digit(s[3]);
+            assert_eq!(
+                Err(crate::LuhnError::InvalidChecksum),
+                crate::decimal::check(&s)
+            );
+            s[5] = b'x';
+            s[9] = b' ';
+            assert_eq!(
+                Err(crate::LuhnError::InvalidByte(5)),
+                crate::decimal::check(&s)
+            );
+        }
+    }
+
+    #[test]
+    fn test_swar_matches_byte_loop() {
+        for sample in DECIMAL_LUHN_SAMPLES {
+            let sample = sample.as_bytes();
+            for len in 0..=sample.len() {
+                let s = &sample[sample.len() - len..];
+                let digits = || s.iter().rev().map(|c| c - b'0');
+                let swar = crate::fold10_swar(0x0201020102010201, 0x7f047f047f047f04, s);
+                assert_eq!(
+                    Some(crate::fold10_iter(false, digits()) % 10),
+                    swar.map(|v| v % 10)
+                );
+                let swar = crate::fold10_swar(0x0102010201020102, 0x047f047f047f047f, s);
+                assert_eq!(
+                    Some(crate::fold10_iter(true, digits()) % 10),
+                    swar.map(|v| v % 10)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_validated_decimal() {
+        use crate::ValidatedDecimal;
+        let card = ValidatedDecimal::try_new(*b"5555555555554444").unwrap();
+        assert!(crate::decimal::valid(&*card));
+        assert_eq!(16, card.len());
+        assert_eq!(None, ValidatedDecimal::try_new(*b"5555555555554445"));
+        assert_eq!(None, ValidatedDecimal::try_new(*b"555555555555444x"));
+        assert!(ValidatedDecimal::try_new([]).is_some());
+    }
+
+    #[test]
+    fn test_decimal_valid_reversed() {
+        for sample in DECIMAL_LUHN_SAMPLES {
+            let mut s = Vec::from(*sample);
+            s.reverse();
+            assert!(crate::decimal::valid_reversed(&s));
+            for i in 0..s.len() {
+                let mut m = s.clone();
+                m[i] = change_digit(m[i]);
+                assert!(!crate::decimal::valid_reversed(&m));
+                m.reverse();
+                assert!(!crate::decimal::valid(&m));
+            }
+        }
+        assert!(!crate::decimal::valid_reversed(b""));
+        assert!(!crate::decimal::valid_reversed(b"x0"));
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn test_decimal_complete_heapless() {
+        for sample in DECIMAL_LUHN_SAMPLES {
+            let body = &sample.as_bytes()[..sample.len() - 1];
+            let full = crate::decimal::complete_heapless::<19>(body).unwrap();
+            assert_eq!(*sample, full.as_str());
+        }
+        let card = crate::decimal::complete_heapless::<16>(b"411111111111111").unwrap();
+        assert_eq!("4111111111111111", card.as_str());
+        assert_eq!(
+            None,
+            crate::decimal::complete_heapless::<16>(b"41111111111111x")
+        );
+    }
+
+    #[test]
+    fn test_decimal_vec_all_lengths() {
+        for len in 0..=64 {
+            let s = (0..len)
+                .map(|i| b'0' + (i as u8 * 3 + len as u8) % 10)
+                .collect::<Vec<_>>();
+            assert_eq!(crate::decimal::valid(&s), crate::decimal::valid_vec(&s));
+            assert_eq!(
+                crate::decimal::checksum(&s),
+                crate::decimal::checksum_vec(&s)
+            );
+            for i in 0..len {
+                let mut bad = s.clone();
+                bad[i] = b'/';
+                assert!(!crate::decimal::valid_vec(&bad));
+                assert_eq!(None, crate::decimal::checksum_vec(&bad));
+                bad[i] = b':';
+                assert!(!crate::decimal::valid_vec(&bad));
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(all(
+        feature = "simd",
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "sse2"
+    ))]
+    fn test_sse2_short_matches_general() {
+        let mut seed = 0x9e3779b97f4a7c15_u64;
+        for len in 0..=16 {
+            for round in 0..20 {
+                let s = (0..len)
+                    .map(|_| {
+                        let seed = xorshift(&mut seed);
+                        // largest possible sum on the first round
+                        if round == 0 {
+                            b'9'
+                        } else {
+                            b'0' + (seed % 10) as u8
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                for correct in [false, true] {
+                    assert_eq!(
+                        crate::sse2::fold10v_sse2(correct, &s),
+                        crate::sse2::fold10v_short(correct, &s)
+                    );
+                }
+            }
+        }
+        assert_eq!(None, crate::sse2::fold10v_short(false, b"40128888x8881881"));
+    }
+
+    #[test]
+    #[cfg(all(
+        feature = "simd",
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "sse2"
+    ))]
+    fn test_sse2_fold36_matches_scalar() {
+        const SYMBOLS: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+        let mut seed = 0x853c49e6748fea9b_u64;
+        for len in 0..=80 {
+            for round in 0..20 {
+                let s = (0..len)
+                    .map(|_| {
+                        let seed = xorshift(&mut seed);
+                        // mostly letters on even rounds, mostly digits on odd ones
+                        let ix = (seed % 36) as usize;
+                        if round % 2 == 0 || ix < 10 || (seed >> 32).is_multiple_of(4) {
+                            SYMBOLS[ix]
+                        } else {
+                            SYMBOLS[ix % 10]
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                for correct in [false, true] {
+                    assert_eq!(
+                        crate::fold36(correct, &s),
+                        crate::sse2::fold36_sse2(correct, &s)
+                    );
+                }
+                if len > 0 {
+                    let mut bad = s.clone();
+                    bad[(seed % len as u64) as usize] = b'a';
+                    assert_eq!(None, crate::sse2::fold36_sse2(true, &bad));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_valid_vec_forward_all_lengths() {
+        let mut seed = 0xda942042e4dd58b5_u64;
+        for len in 0..40 {
+            for round in 0..10 {
+                let mut s = (0..len)
+                    .map(|_| {
+                        let seed = xorshift(&mut seed);
+                        b'0' + (seed % 10) as u8
+                    })
+                    .collect::<Vec<_>>();
+                // make roughly half of the inputs valid
+                if round % 2 == 0 {
+                    crate::decimal::set_check_digit(&mut s);
+                }
+                assert_eq!(
+                    !s.is_empty() && crate::decimal::valid_vec(&s),
+                    crate::decimal::valid_vec_forward(&s)
+                );
+                // SWAR fold used for short inputs agrees only modulo 10
+                assert_eq!(
+                    crate::fold10v(true, &s).map(|d| d % 10),
+                    crate::fold10v_forward(true, &s).map(|d| d % 10)
+                );
+                if len > 0 {
+                    let mut bad = s.clone();
+                    bad[(seed % len as u64) as usize] = b':';
+                    assert!(!crate::decimal::valid_vec_forward(&bad));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_mixer_matches_vec() {
+        let long = b"4012888888881881".repeat(5);
+        let samples = DECIMAL_LUHN_SAMPLES.iter().map(|s| s.as_bytes());
+        for sample in samples.chain([&long[..]]) {
+            // every prefix covers both parities and lengths on both sides of SIMD thresholds
+            let mut m = crate::Mixer::default();
+            for (i, c) in sample.iter().enumerate() {
+                let body = &sample[..i];
+                assert_eq!(crate::decimal::checksum_vec(body), Some(m.checksum()));
+                assert_eq!(crate::decimal::checksum(body), Some(m.checksum()));
+                m.push(c - b'0');
+                let prefix = &sample[..=i];
+                assert_eq!(crate::decimal::valid_vec(prefix), m.valid());
+                assert_eq!(crate::decimal::valid(prefix), m.valid());
+            }
+            assert!(m.valid());
+        }
+        let m = crate::Mixer::default();
+        assert!(crate::decimal::valid_vec(b""));
+        assert!(!m.valid());
+        assert_eq!(crate::decimal::checksum_vec(b""), Some(m.checksum()));
+    }
+
+    #[test]
+    fn test_checksum_mod() {
+        for sample in DECIMAL_LUHN_SAMPLES.iter().map(|s| s.as_bytes()) {
+            let (_, body) = sample.split_last().unwrap();
+            assert_eq!(
+                crate::decimal::checksum(body),
+                crate::decimal::checksum_mod(body, 10)
+            );
+            for modulus in 1..=10 {
+                let check = crate::decimal::checksum_mod(body, modulus).unwrap();
+                let mut full = body.to_vec();
+                full.push(check);
+                let sum = crate::fold10_ascii(false, full.iter().rev().copied()).unwrap();
+                assert_eq!(0, sum % u64::from(modulus));
+            }
+            assert_eq!(None, crate::decimal::checksum_mod(body, 0));
+            assert_eq!(None, crate::decimal::checksum_mod(body, 11));
+        }
+        assert_eq!(None, crate::decimal::checksum_mod(b"US594918104", 10));
+    }
+
+    #[test]
+    fn test_decimal_fold_chunk() {
+        for sample in DECIMAL_LUHN_SAMPLES.iter().map(|s| s.as_bytes()) {
+            for mid in 0..=sample.len() {
+                let (left, right) = sample.split_at(mid);
+                let (r, double) = crate::decimal::fold_chunk(right, false).unwrap();
+                let (l, _) = crate::decimal::fold_chunk(left, double).unwrap();
+                assert_eq!(0, (l + r) % 10);
+
+                // same for computing a check digit
+                let body = &sample[..sample.len() - 1];
+                let (left, right) = body.split_at(mid.min(body.len()));
+                let (r, double) = crate::decimal::fold_chunk(right, true).unwrap();
+                let (l, _) = crate::decimal::fold_chunk(left, double).unwrap();
+                let check = b'0' + ((10 - (l + r) % 10) % 10) as u8;
+                assert_eq!(sample.last(), Some(&check));
+            }
+        }
+        assert_eq!(None, crate::decimal::fold_chunk(b"12x4", false));
+    }
+
+    #[test]
+    fn test_decimal_valid_strided() {
+        let mut records = DECIMAL_LUHN_SAMPLES
+            .iter()
+            .filter(|s| s.len() == 16)
+            .map(|s| Vec::from(*s))
+            .collect::<Vec<_>>();
+        for r in records.iter_mut().step_by(3) {
+            r[7] = change_digit(r[7]);
+        }
+        records[4][2] = b'x';
+        let stride = records.len() + 2;
+        let mut data = vec![b'0'; 16 * stride];
+        for (r, record) in records.iter().enumerate() {
+            for (i, c) in record.iter().enumerate() {
+                data[i * stride + r] = *c;
+            }
+        }
+        let mask = crate::decimal::valid_strided(&data, records.len(), stride);
+        for (r, record) in records.iter().enumerate() {
+            assert_eq!(crate::decimal::valid(record), mask & (1 << r) != 0);
+        }
+        assert_eq!(0, mask >> records.len());
+        assert_eq!(0, crate::decimal::valid_strided(&data[1..], 3, stride));
+        assert_eq!(0, crate::decimal::valid_strided(&data, stride + 1, stride));
+    }
+
+    #[test]
+    fn test_validated_try_from() {
+        use crate::{LuhnError, ValidatedAlphanum, ValidatedDecimal};
+        for sample in DECIMAL_LUHN_SAMPLES.iter().filter(|s| s.len() == 16) {
+            let card = ValidatedDecimal::<16>::try_from(*sample).unwrap();
+            assert_eq!(Ok(card), ValidatedDecimal::try_from(sample.as_bytes()));
+            assert_eq!(Ok(card), sample.parse());
+
+            let mut s = Vec::from(*sample);
+            s[3] = change_digit(s[3]);
+            let res = ValidatedDecimal::<16>::try_from(s.as_slice());
+            assert_eq!(Err(LuhnError::InvalidChecksum), res);
+            s[3] = b'x';
+            let res = ValidatedDecimal::<16>::try_from(s.as_slice());
+            assert_eq!(Err(LuhnError::InvalidByte(3)), res);
+            let res = ValidatedDecimal::<15>::try_from(*sample);
+            assert_eq!(Err(LuhnError::InvalidLength), res);
+        }
+
+        for sample in ALPHANUM_LUHN_SAMPLES {
+            let isin = ValidatedAlphanum::<12>::try_from(*sample).unwrap();
+            assert_eq!(sample.as_bytes(), isin.as_bytes());
+            let res = ValidatedAlphanum::<12>::try_from(&sample.as_bytes()[1..]);
+            assert_eq!(Err(LuhnError::InvalidLength), res);
+            let res = ValidatedAlphanum::<12>::try_from(sample.to_lowercase().as_str());
+            assert!(matches!(res, Err(LuhnError::InvalidByte(_))));
+        }
+        let res = ValidatedAlphanum::<12>::try_from("US5949181046");
+        assert_eq!(Err(LuhnError::InvalidChecksum), res);
+    }
+
+    #[test]
+    fn test_decimal_explain() {
+        for sample in DECIMAL_LUHN_SAMPLES.iter().map(|s| s.as_bytes()) {
+            let e = crate::decimal::explain(sample).unwrap();
+            assert!(e.valid());
+            assert_eq!(0, e.residual());
+            assert_eq!(e.total, e.weighted().map(u64::from).sum::<u64>());
+            assert_eq!(sample.len(), e.weighted().count());
+
+            let mut s = Vec::from(sample);
+            s[3] = change_digit(s[3]);
+            let e = crate::decimal::explain(&s).unwrap();
+            assert!(!e.valid());
+            assert_ne!(0, e.residual());
+            assert_eq!(e.total, e.weighted().map(u64::from).sum::<u64>());
+        }
+        assert_eq!(None, crate::decimal::explain(b""));
+        assert_eq!(None, crate::decimal::explain(b"x1"));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_decimal_par_valid() {
+        let items = (0..100_000u64)
+            .map(|i| format!("{:016}", i * 7919).into_bytes())
+            .collect::<Vec<_>>();
+        let items = items.iter().map(|s| s.as_slice()).collect::<Vec<_>>();
+        let expected = items
+            .iter()
+            .map(|s| crate::decimal::valid(s))
+            .collect::<Vec<_>>();
+        assert_eq!(expected, crate::decimal::par_valid(&items));
+    }
+
+    #[test]
+    fn test_decimal_valid_nontrivial() {
+        for sample in DECIMAL_LUHN_SAMPLES {
+            assert!(crate::decimal::valid_nontrivial(sample.as_bytes()));
+        }
+        for len in 1..20 {
+            let zeros = vec![b'0'; len];
+            assert!(crate::decimal::valid(&zeros));
+            assert!(!crate::decimal::valid_nontrivial(&zeros));
+        }
+        assert!(!crate::decimal::valid_nontrivial(b""));
+    }
+
+    #[test]
+    fn test_decimal_checksum2() {
+        for sample in DECIMAL_LUHN_SAMPLES {
+            for len in 0..sample.len() {
+                let mut s = Vec::from(&sample.as_bytes()[..len]);
+                let [first, second] = crate::decimal::checksum2(&s).unwrap();
+                s.push(first);
+                assert!(crate::decimal::valid(&s));
+                s.push(second);
+                assert!(crate::decimal::valid(&s));
+            }
+        }
+        assert_eq!(None, crate::decimal::checksum2(b"US594918104"));
+    }
+
+    #[test]
+    fn test_decimal_fill_valid() {
+        for sample in DECIMAL_LUHN_SAMPLES.iter().map(|s| s.as_bytes()) {
+            for len in 0..sample.len() {
+                let prefix = &sample[..len];
+                let mut buf = vec![b'?'; sample.len()];
+                let check = crate::decimal::fill_valid(&mut buf, prefix).unwrap();
+                assert!(crate::decimal::valid(&buf));
+                assert!(buf.starts_with(prefix));
+                assert_eq!(buf.last(), Some(&check));
+            }
+            let mut buf = vec![b'?'; sample.len()];
+            assert_eq!(None, crate::decimal::fill_valid(&mut buf, sample));
+            assert_eq!(None, crate::decimal::fill_valid(&mut buf, b"41x"));
+            assert!(buf.iter().all(|c| *c == b'?'));
+        }
+        assert_eq!(None, crate::decimal::fill_valid(&mut [], b""));
+    }
+
+    /// Scalar and vectorized `valid` for each input along with its index
+    fn audit(inputs: &[&[u8]]) -> Vec<(usize, bool, bool)> {
+        inputs
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (i, crate::decimal::valid(s), crate::decimal::valid_vec(s)))
+            .collect()
+    }
+
+    #[test]
+    fn test_audit_scalar_and_vec() {
+        let mut inputs = vec![Vec::new()];
+        let mut seed = 0x2545f4914f6cdd1du64;
+        for sample in DECIMAL_LUHN_SAMPLES.iter().map(|s| s.as_bytes()) {
+            inputs.push(Vec::from(sample));
+            for _ in 0..64 {
+                let seed = xorshift(&mut seed);
+                let mut s = Vec::from(sample);
+                let (a, b) = (seed as usize % s.len(), (seed >> 32) as usize % s.len());
+                s.swap(a, b);
+                s[a] = match seed >> 60 {
+                    0 => b'x',
+                    1 => s[a].wrapping_add(10),
+                    _ => s[a],
+                };
+                let len = (seed >> 20) as usize % (s.len() * 3);
+                s = s.iter().cycle().take(len).copied().collect();
+                inputs.push(s);
+            }
         }
+        let inputs = inputs.iter().map(|s| s.as_slice()).collect::<Vec<_>>();
+        let divergences = audit(&inputs)
+            .into_iter()
+            .filter(|(_, scalar, vec)| scalar != vec)
+            .collect::<Vec<_>>();
+        assert_eq!(Vec::<(usize, bool, bool)>::new(), divergences);
     }
 
     #[test]
-    fn test_ae_checksum() {
-        let (&check, body) = b"378282246310005".split_last().unwrap();
-        assert_eq!(Some(check), crate::decimal::checksum(body));
+    fn test_valid_line() {
+        assert!(crate::decimal::valid_line(b"4111111111111111\r\n"));
+        assert!(crate::decimal::valid_line(b"4111111111111111\n"));
+        assert!(!crate::decimal::valid_line(b"4111111111111111 "));
+        assert!(!crate::decimal::valid_line(b"4111111111111111\r"));
+        assert!(!crate::decimal::valid_line(b" 4111111111111111\n"));
+        assert!(crate::decimal::valid_line(b"\r\n"));
     }
 
     #[test]
-    fn test_decimal_luhn_checksum() {
-        for sample in DECIMAL_LUHN_SAMPLES {
-            // number is valid as is valid
-            assert!(crate::decimal::valid(sample.as_bytes()));
+    fn test_solve_wildcard() {
+        for sample in DECIMAL_LUHN_SAMPLES.iter().map(|s| s.as_bytes()) {
+            for pos in 0..sample.len() {
+                let mut smudged = sample.to_vec();
+                smudged[pos] = b'?';
+                assert_eq!(Some(sample[pos]), crate::decimal::solve_wildcard(&smudged));
+            }
+        }
+        assert_eq!(None, crate::decimal::solve_wildcard(b"4012888888881881"));
+        assert_eq!(None, crate::decimal::solve_wildcard(b"?0128888888818?1"));
+        assert_eq!(None, crate::decimal::solve_wildcard(b"x012888888881?81"));
+    }
 
-            // luhn checksum detects a single changed digit
-            let mut s = Vec::from(*sample);
-            s[3] = change_digit(s[3]);
-            assert!(!crate::decimal::valid(&s));
+    #[test]
+    fn test_fill_placeholder() {
+        for sample in DECIMAL_LUHN_SAMPLES.iter().map(|s| s.as_bytes()) {
+            let mut template = sample.to_vec();
+            *template.last_mut().unwrap() = b'*';
+            let check = crate::decimal::fill_placeholder(&mut template);
+            assert_eq!(sample.last().copied(), check);
+            assert_eq!(sample, template);
+        }
+        let mut template = *b"4012888*8888188*";
+        assert_eq!(None, crate::decimal::fill_placeholder(&mut template));
+        assert_eq!(b"4012888*8888188*", &template);
+        let mut template = *b"40128888x888188*";
+        assert_eq!(None, crate::decimal::fill_placeholder(&mut template));
+    }
 
-            // luhn checksum also detects two digit swap
-            let mut s = Vec::from(*sample);
-            if s[3] != s[4] {
-                s.swap(3, 4);
-                assert!(!crate::decimal::valid(&s));
+    #[test]
+    fn test_residual() {
+        for sample in DECIMAL_LUHN_SAMPLES.iter().map(|s| s.as_bytes()) {
+            assert_eq!(Some(0), crate::decimal::residual(sample));
+            for pos in 0..sample.len() {
+                let mut changed = sample.to_vec();
+                changed[pos] = change_digit(changed[pos]);
+                assert_ne!(Some(0), crate::decimal::residual(&changed));
             }
+        }
+    }
 
-            // last digit is it's luhn checksum
-            let (checksum, body) = sample.as_bytes().split_last().unwrap();
-            assert_eq!(Some(*checksum), crate::decimal::checksum(body));
+    #[test]
+    fn test_scan_matches_windows() {
+        let mut text = Vec::new();
+        let mut seed = 0x9e3779b97f4a7c15u64;
+        for _ in 0..5000 {
+            let seed = xorshift(&mut seed);
+            match seed % 8 {
+                0 => text.extend_from_slice(b"lorem ipsum dolor sit amet "),
+                1 => text.extend_from_slice(
+                    DECIMAL_LUHN_SAMPLES[(seed >> 8) as usize % DECIMAL_LUHN_SAMPLES.len()]
+                        .as_bytes(),
+                ),
+                2 => text.push(b' '),
+                _ => text.push(b'0' + (seed >> 16) as u8 % 10),
+            }
+        }
+        for len in [0, 1, 2, 13, 16, 19] {
+            let expected = (0..=text.len().saturating_sub(len))
+                .filter(|&i| len > 0 && crate::decimal::valid(&text[i..i + len]))
+                .collect::<Vec<_>>();
+            let scalar = crate::decimal::scan(&text, len).collect::<Vec<_>>();
+            let vec = crate::decimal::scan_vec(&text, len).collect::<Vec<_>>();
+            assert_eq!(expected, scalar, "{len}");
+            assert_eq!(expected, vec, "{len}");
+        }
+        assert_eq!(None, crate::decimal::scan_vec(b"0", 2).next());
+        assert_eq!(None, crate::decimal::scan(b"", 2).next());
+    }
 
-            // and finally only decimal numbers are accepted
-            let mut s = Vec::from(*sample);
-            s[3] = b'x';
+    #[test]
+    fn test_card_number_states() {
+        use crate::card::CardNumber;
+        for sample in DECIMAL_LUHN_SAMPLES.iter().map(|s| s.as_bytes()) {
+            let (check, body) = sample.split_last().unwrap();
+            let card = CardNumber::body(body).unwrap();
+            assert_eq!(*check, card.checksum());
+            let card = card.with_check_digit();
+            assert_eq!(sample, card.as_bytes());
+            assert_eq!(*check, card.check_digit());
+            assert_eq!(Some(card), CardNumber::full(sample));
+            assert!(card.valid());
+        }
+        assert!(CardNumber::body(b"").is_none());
+        assert!(CardNumber::body(b"401288888888188888").is_some());
+        assert!(CardNumber::body(b"4012888888881888888").is_none());
+        assert!(CardNumber::full(b"4012888888881888888").is_some());
+        assert!(CardNumber::full(b"40128888888818888888").is_none());
+    }
+
+    #[test]
+    fn test_reject_bad_byte_in_right_most_chunk() {
+        // all zeros is a valid number, anything rejected below is rejected for the bad byte
+        let mut digits = vec![b'0'; 1 << 12];
+        assert!(crate::decimal::valid(&digits));
+        assert!(crate::decimal::valid_vec(&digits));
+        for pos in (digits.len() - 32..digits.len()).chain([0, 1 << 11]) {
+            digits[pos] = b'x';
+            assert!(!crate::decimal::valid(&digits), "{pos}");
+            assert!(!crate::decimal::valid_vec(&digits), "{pos}");
+            digits[pos] = b'0';
+        }
+    }
+
+    #[test]
+    fn test_mixer_combine() {
+        for sample in DECIMAL_LUHN_SAMPLES.iter().map(|s| s.as_bytes()) {
+            for split in 0..=sample.len() {
+                let mut left = crate::Mixer::default();
+                let mut right = crate::Mixer::default();
+                let (l, r) = sample.split_at(split);
+                l.iter().for_each(|c| left.push(c - b'0'));
+                r.iter().for_each(|c| right.push(c - b'0'));
+                let mixer = left.combine(right);
+                assert!(mixer.valid());
+                assert_eq!(sample.len(), mixer.len());
+
+                // same parts without the check digit
+                let mut left = crate::Mixer::default();
+                let mut right = crate::Mixer::default();
+                let (l, r) = sample[..sample.len() - 1].split_at(split.min(sample.len() - 1));
+                l.iter().for_each(|c| left.push(c - b'0'));
+                r.iter().for_each(|c| right.push(c - b'0'));
+                assert_eq!(sample.last().copied(), Some(left.combine(right).checksum()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_checksum_str_buf() {
+        for &sample in DECIMAL_LUHN_SAMPLES {
+            let mut buf = [b'x'; 32];
+            let body = &sample.as_bytes()[..sample.len() - 1];
+            assert_eq!(
+                Some(sample),
+                crate::decimal::checksum_str_buf(body, &mut buf)
+            );
+            assert_eq!(b'x', buf[sample.len()]);
+        }
+        let mut buf = [b'x'; 4];
+        assert_eq!(None, crate::decimal::checksum_str_buf(b"12x", &mut buf));
+        assert_eq!(Some("0"), crate::decimal::checksum_str_buf(b"", &mut buf));
+        assert_eq!(None, crate::decimal::checksum_str_buf(b"...", &mut [0; 0]));
+    }
+
+    #[test]
+    fn test_valid_ignoring() {
+        for sample in DECIMAL_LUHN_SAMPLES.iter().map(|s| s.as_bytes()) {
+            let mut formatted = Vec::new();
+            for (i, c) in sample.iter().enumerate() {
+                formatted.push(*c);
+                formatted.push(b" ,."[i % 3]);
+            }
+            assert!(crate::decimal::valid_ignoring(&formatted, b" ,."));
+            assert!(!crate::decimal::valid_ignoring(&formatted, b" ,"));
+            *formatted.last_mut().unwrap() = b'-';
+            assert!(!crate::decimal::valid_ignoring(&formatted, b" ,."));
+        }
+        assert!(!crate::decimal::valid_ignoring(b"", b" ,."));
+    }
+
+    #[test]
+    fn test_vec_across_chunks_19_digits() {
+        let card = b"6205500000000000004";
+        assert!(crate::decimal::valid_vec(card));
+        assert_eq!(Some(b'4'), crate::decimal::checksum_vec(&card[..18]));
+        // every digit on either side of the 16 byte boundary
+        for pos in 0..card.len() {
+            for digit in b'0'..=b'9' {
+                let mut s = *card;
+                s[pos] = digit;
+                assert_eq!(crate::decimal::valid(&s), crate::decimal::valid_vec(&s));
+                assert_eq!(
+                    crate::decimal::checksum(&s[..18]),
+                    crate::decimal::checksum_vec(&s[..18])
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_luhn_traits_match_functions() {
+        use crate::{Luhn, LuhnAlnum};
+        for sample in DECIMAL_LUHN_SAMPLES.iter().chain(ALPHANUM_LUHN_SAMPLES) {
+            let bytes = sample.as_bytes();
+            let (_, body) = bytes.split_last().unwrap();
+            assert_eq!(crate::decimal::valid(bytes), sample.luhn_valid());
+            assert_eq!(crate::decimal::valid(bytes), bytes.luhn_valid());
+            assert_eq!(crate::decimal::checksum(body), body.luhn_checksum());
+            assert_eq!(crate::alphanum::valid(bytes), sample.luhn_alnum_valid());
+            assert_eq!(crate::alphanum::valid(bytes), bytes.luhn_alnum_valid());
+            assert_eq!(crate::alphanum::checksum(body), body.luhn_alnum_checksum());
+        }
+        assert!(b"4012888888881881".luhn_valid());
+        assert!(b"US5949181045".luhn_alnum_valid());
+        assert!("".luhn_valid());
+    }
+
+    #[test]
+    fn test_luhn_iter_filters_valid() {
+        use crate::LuhnIter;
+        let input = DECIMAL_LUHN_SAMPLES
+            .iter()
+            .chain(ALPHANUM_LUHN_SAMPLES)
+            .flat_map(|s| [s.to_string(), s.replace('0', "1")])
+            .collect::<Vec<_>>();
+        let decimal = input.iter().luhn_valid_decimal().collect::<Vec<_>>();
+        let expected = input
+            .iter()
+            .filter(|s| crate::decimal::valid(s.as_bytes()))
+            .collect::<Vec<_>>();
+        assert!(!decimal.is_empty() && decimal.len() < input.len());
+        assert_eq!(expected, decimal);
+
+        let alphanum = input.iter().luhn_valid_alphanum().collect::<Vec<_>>();
+        let expected = input
+            .iter()
+            .filter(|s| crate::alphanum::valid(s.as_bytes()))
+            .collect::<Vec<_>>();
+        assert!(alphanum.len() > decimal.len());
+        assert_eq!(expected, alphanum);
+
+        let slices = [&b"US5949181045"[..], b"79927398713", b"x"];
+        assert_eq!(1, slices.into_iter().luhn_valid_decimal().count());
+    }
+
+    #[test]
+    fn test_valid_const_matches_valid() {
+        crate::assert_valid!("79927398713");
+        for sample in DECIMAL_LUHN_SAMPLES.iter().chain(ALPHANUM_LUHN_SAMPLES) {
+            let bytes = sample.as_bytes();
+            assert_eq!(
+                crate::decimal::valid(bytes),
+                crate::decimal::valid_const(bytes)
+            );
+            for i in 0..bytes.len() {
+                let mut s = bytes.to_vec();
+                s[i] = change_digit(s[i]);
+                assert_eq!(crate::decimal::valid(&s), crate::decimal::valid_const(&s));
+            }
+        }
+        assert!(!crate::decimal::valid_const(b"0x"));
+    }
+
+    #[test]
+    fn test_alphanum_checksum_vec_matches_scalar() {
+        for sample in DECIMAL_LUHN_SAMPLES.iter().chain(ALPHANUM_LUHN_SAMPLES) {
+            let bytes = sample.as_bytes();
+            for len in 0..=bytes.len() {
+                let body = &bytes[..len];
+                assert_eq!(
+                    crate::alphanum::checksum(body),
+                    crate::alphanum::checksum_vec(body)
+                );
+            }
+            let (_, body) = bytes.split_last().unwrap();
+            let mut s = body.to_vec();
+            s.extend_from_slice(b"A0123456789012345678");
+            assert_eq!(
+                crate::alphanum::checksum(&s),
+                crate::alphanum::checksum_vec(&s)
+            );
+            s.push(b'a');
+            assert_eq!(None, crate::alphanum::checksum_vec(&s));
+        }
+    }
+
+    #[test]
+    fn test_nul_bytes() {
+        use core::ffi::CStr;
+        for sample in DECIMAL_LUHN_SAMPLES {
+            let mut s = sample.as_bytes().to_vec();
+            s.push(0);
+            let cstr = CStr::from_bytes_with_nul(&s).unwrap();
+            assert!(crate::decimal::valid_cstr(cstr));
+            // NUL is not a digit anywhere in a slice
             assert!(!crate::decimal::valid(&s));
+            assert!(!crate::decimal::valid_vec(&s));
+            for i in 0..s.len() - 1 {
+                let mut t = s.clone();
+                t[i] = 0;
+                assert!(!crate::decimal::valid(&t[..t.len() - 1]));
+                let cstr = CStr::from_bytes_until_nul(&t).unwrap();
+                assert_eq!(
+                    crate::decimal::valid(&t[..i]),
+                    crate::decimal::valid_cstr(cstr)
+                );
+            }
+        }
+        let cstr = CStr::from_bytes_until_nul(b"79927398713\x004012888888881882\0").unwrap();
+        assert!(crate::decimal::valid_cstr(cstr));
+        assert!(crate::decimal::valid_cstr(c""));
+    }
+
+    #[test]
+    fn test_classify() {
+        use crate::{classify, InputKind};
+        for sample in DECIMAL_LUHN_SAMPLES {
+            assert_eq!(InputKind::Decimal, classify(sample.as_bytes()));
+        }
+        for sample in ALPHANUM_LUHN_SAMPLES {
+            let bytes = sample.as_bytes();
+            let expected = if bytes.iter().all(u8::is_ascii_digit) {
+                InputKind::Decimal
+            } else {
+                InputKind::Alphanumeric
+            };
+            assert_eq!(expected, classify(bytes));
+            assert_eq!(
+                crate::valid_fast(bytes),
+                classify(bytes) != InputKind::Invalid
+            );
+        }
+        for bad in [
+            &b""[..],
+            b"banana",
+            b"4012888888881882",
+            b"US5949181046",
+            b"us5949181045",
+        ] {
+            assert_eq!(InputKind::Invalid, classify(bad));
+        }
+    }
+
+    #[test]
+    fn test_valid_range() {
+        for sample in DECIMAL_LUHN_SAMPLES {
+            let buf = format!("<{}>", sample);
+            let buf = buf.as_bytes();
+            for start in 0..buf.len() + 2 {
+                for end in 0..buf.len() + 2 {
+                    let expected = buf.get(start..end).is_some_and(crate::decimal::valid);
+                    assert_eq!(expected, crate::decimal::valid_range(buf, start..end));
+                }
+            }
+            assert!(crate::decimal::valid_range(buf, 1..buf.len() - 1));
+            assert!(!crate::decimal::valid_range(buf, 1..buf.len() + 1));
+        }
+        let reversed = core::ops::Range { start: 5, end: 2 };
+        assert!(!crate::decimal::valid_range(b"79927398713", reversed));
+    }
+
+    #[test]
+    fn test_validated_eq_str_and_bytes() {
+        use crate::{ValidatedAlphanum, ValidatedDecimal};
+        let card = ValidatedDecimal::try_new(*b"4012888888881881").unwrap();
+        assert_eq!(card, "4012888888881881");
+        assert_eq!("4012888888881881", card);
+        assert_eq!(card, *"4012888888881881");
+        assert_eq!(*"4012888888881881", card);
+        assert_eq!(card, b"4012888888881881"[..]);
+        assert_eq!(b"4012888888881881"[..], card);
+        assert_ne!(card, "4012888888881882");
+        assert_ne!("4012888888881882", card);
+        assert_ne!(card, "401288888888188");
+        assert_ne!(b"40128888888818810"[..], card);
+
+        let isin = ValidatedAlphanum::try_new(*b"US5949181045").unwrap();
+        assert_eq!(isin, "US5949181045");
+        assert_eq!("US5949181045", isin);
+        assert_eq!(isin, b"US5949181045"[..]);
+        assert_eq!(b"US5949181045"[..], isin);
+        assert_ne!(isin, "us5949181045");
+        assert_ne!("us5949181045", isin);
+    }
+
+    #[test]
+    fn test_valid_chars() {
+        for sample in DECIMAL_LUHN_SAMPLES.iter().chain(ALPHANUM_LUHN_SAMPLES) {
+            assert_eq!(
+                crate::decimal::valid(sample.as_bytes()),
+                crate::decimal::valid_chars(sample.chars())
+            );
+            for (i, c) in sample.char_indices() {
+                // same digit in a non ASCII form
+                let mut s = sample.to_string();
+                s.replace_range(
+                    i..=i,
+                    &char::from_u32(c as u32 + 0xfee0).unwrap().to_string(),
+                );
+                assert!(!crate::decimal::valid_chars(s.chars()));
+            }
+        }
+        assert!(!crate::decimal::valid_chars("口水鸡".chars()));
+        assert!(crate::decimal::valid_chars(
+            ['7', '9', '9', '2', '7', '3', '9', '8', '7', '1', '3'].into_iter()
+        ));
+    }
+
+    #[test]
+    fn test_alphanum_valid_relaxed() {
+        for sample in DECIMAL_LUHN_SAMPLES.iter().chain(ALPHANUM_LUHN_SAMPLES) {
+            assert!(crate::alphanum::valid_relaxed(sample.as_bytes()));
+            let messy = sample
+                .to_ascii_lowercase()
+                .bytes()
+                .enumerate()
+                .flat_map(|(i, c)| match i % 3 {
+                    0 => vec![c],
+                    1 => vec![b' ', c],
+                    _ => vec![c, b'-', b' '],
+                })
+                .collect::<Vec<_>>();
+            assert!(crate::alphanum::valid_relaxed(&messy));
+            let mut bad = messy.clone();
+            bad.push(b'_');
+            assert!(!crate::alphanum::valid_relaxed(&bad));
+        }
+        assert!(!crate::alphanum::valid_relaxed(b"us 5949 1810 46"));
+        assert!(!crate::alphanum::valid_relaxed(b""));
+    }
+
+    #[test]
+    fn test_alphanum_weighted() {
+        let mut out = [0; 64];
+        for sample in DECIMAL_LUHN_SAMPLES.iter().chain(ALPHANUM_LUHN_SAMPLES) {
+            let bytes = sample.as_bytes();
+            let len = crate::alphanum::weighted(bytes, &mut out).unwrap();
+            let total = out[..len].iter().map(|&w| u64::from(w)).sum::<u64>();
+            assert_eq!(crate::fold36(false, bytes), Some(total));
+            assert_eq!(0, total % 10);
+            if bytes.iter().all(u8::is_ascii_digit) {
+                let mut decimal = [0; 64];
+                crate::decimal::weighted(bytes, &mut decimal).unwrap();
+                assert_eq!(decimal[..len], out[..len]);
+            }
+            let mut short = [0xff; 12];
+            match crate::alphanum::weighted(bytes, &mut short) {
+                Some(len) => assert!(len <= 12),
+                None => assert_eq!([0xff; 12], short),
+            }
+        }
+        assert_eq!(None, crate::alphanum::weighted(b"us5949181045", &mut out));
+        assert_eq!(Some(0), crate::alphanum::weighted(b"", &mut out));
+    }
+
+    #[test]
+    fn test_has_luhn_blindspot() {
+        for sample in DECIMAL_LUHN_SAMPLES.iter().map(|s| s.as_bytes()) {
+            // swapping a pair must go unnoticed exactly when it's a blind spot
+            let mut undetected = false;
+            for i in 0..sample.len() - 1 {
+                let mut s = sample.to_vec();
+                s.swap(i, i + 1);
+                if s != sample && crate::decimal::valid(&s) {
+                    undetected = true;
+                }
+            }
+            assert_eq!(undetected, crate::decimal::has_luhn_blindspot(sample));
+        }
+        assert!(crate::decimal::has_luhn_blindspot(b"90"));
+        assert!(!crate::decimal::has_luhn_blindspot(b"9"));
+        assert!(!crate::decimal::has_luhn_blindspot(b"9119"));
+    }
+
+    #[test]
+    fn test_alphanum_complete_into() {
+        let mut out = [0; 32];
+        for sample in DECIMAL_LUHN_SAMPLES.iter().chain(ALPHANUM_LUHN_SAMPLES) {
+            let body = &sample.as_bytes()[..sample.len() - 1];
+            let lower = body.to_ascii_lowercase();
+            let len = crate::alphanum::complete_into(&lower, &mut out, true).unwrap();
+            assert_eq!(sample.as_bytes(), &out[..len]);
+            let len = crate::alphanum::complete_into(body, &mut out, false).unwrap();
+            assert_eq!(sample.to_ascii_lowercase().as_bytes(), &out[..len]);
+
+            let mut short = [0xff; 32];
+            match crate::alphanum::complete_into(body, &mut short[..body.len()], true) {
+                None => assert_eq!([0xff; 32], short),
+                Some(_) => panic!("no space for a check digit"),
+            }
+        }
+        assert_eq!(Some(1), crate::alphanum::complete_into(b"", &mut out, true));
+        assert_eq!(b'0', out[0]);
+        assert_eq!(
+            None,
+            crate::alphanum::complete_into(b"US-594918104", &mut out, true)
+        );
+    }
+
+    #[test]
+    fn test_root_reexport() {
+        let decimal = cfg!(feature = "default-decimal");
+        assert_eq!(!decimal, crate::valid(b"US5949181045"));
+        assert_eq!(!decimal, crate::checksum(b"US594918104").is_some());
+        assert!(crate::valid(b"4012888888881881"));
+    }
+
+    #[test]
+    fn test_valid_fast() {
+        for sample in DECIMAL_LUHN_SAMPLES.iter().chain(ALPHANUM_LUHN_SAMPLES) {
+            let mut s = sample.as_bytes().to_vec();
+            assert!(crate::valid_fast(&s));
+            for pos in 0..s.len() {
+                s[pos] = change_digit(s[pos]);
+                assert_eq!(crate::alphanum::valid(&s), crate::valid_fast(&s));
+                if s.iter().all(u8::is_ascii_digit) {
+                    assert_eq!(crate::decimal::valid(&s), crate::valid_fast(&s));
+                }
+            }
+        }
+        assert!(!crate::valid_fast(b"us5949181045"));
+    }
+
+    #[test]
+    fn test_set_check_digit() {
+        for sample in DECIMAL_LUHN_SAMPLES.iter().map(|s| s.as_bytes()) {
+            let mut number = sample.to_vec();
+            *number.last_mut().unwrap() = change_digit(*sample.last().unwrap());
+            assert!(crate::decimal::set_check_digit(&mut number));
+            assert!(crate::decimal::valid(&number));
+            assert_eq!(sample, number);
+        }
+        let mut number = *b"4x12888888881880";
+        assert!(!crate::decimal::set_check_digit(&mut number));
+        assert_eq!(b"4x12888888881880", &number);
+    }
+
+    #[test]
+    fn test_normalize_and_validate() {
+        let mut out = [0; 16];
+        for sample in DECIMAL_LUHN_SAMPLES.iter().map(|s| s.as_bytes()) {
+            let formatted = sample.chunks(4).collect::<Vec<_>>().join(&b" "[..]);
+            let res = crate::decimal::normalize_and_validate(&formatted, &mut out);
+            if sample.len() > out.len() {
+                assert_eq!(None, res);
+            } else {
+                assert_eq!(Some(sample.len()), res);
+                assert_eq!(sample, &out[..sample.len()]);
+            }
+        }
+        assert_eq!(
+            Some(0),
+            crate::decimal::normalize_and_validate(b"  -", &mut out)
+        );
+        assert_eq!(
+            None,
+            crate::decimal::normalize_and_validate(b"4012.8888.8888.1881", &mut out)
+        );
+    }
+
+    #[test]
+    fn test_mixer_push_checked() {
+        use crate::MixerError;
+        let mut m = crate::Mixer::default();
+        for c in b"4012888888881881" {
+            assert_eq!(Ok(()), m.push_checked(*c, 16));
+        }
+        assert!(m.valid());
+        for c in b"0000" {
+            assert_eq!(Err(MixerError::TooLong), m.push_checked(*c, 16));
+        }
+        assert!(m.valid());
+        assert_eq!(16, m.len());
+
+        let mut m = crate::Mixer::default();
+        assert_eq!(Err(MixerError::TooLong), m.push_checked(b'0', 0));
+        assert_eq!(Err(MixerError::InvalidByte(b'A')), m.push_checked(b'A', 1));
+        assert!(m.is_empty());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_format_grouped() {
+        use crate::decimal::format_grouped;
+        for sample in DECIMAL_LUHN_SAMPLES.iter().map(|s| s.as_bytes()) {
+            let formatted = format_grouped(sample, 4, b' ').unwrap();
+            assert!(crate::decimal::valid_ignoring(formatted.as_bytes(), b" "));
+            assert!(formatted.split(' ').all(|g| (1..=4).contains(&g.len())));
+        }
+        assert_eq!(Some("4111"), format_grouped(b"4111", 4, b' ').as_deref());
+        assert_eq!(Some(""), format_grouped(b"", 4, b' ').as_deref());
+        assert_eq!(None, format_grouped(b"4111", 0, b' '));
+        assert_eq!(None, format_grouped(b"4111 1111", 4, b' '));
+    }
+
+    #[test]
+    fn test_weighted_matches_explain() {
+        let mut out = [0; 32];
+        for sample in DECIMAL_LUHN_SAMPLES.iter().map(|s| s.as_bytes()) {
+            crate::decimal::weighted(sample, &mut out).unwrap();
+            let e = crate::decimal::explain(sample).unwrap();
+            let out = &out[..sample.len()];
+            assert_eq!(e.total, out.iter().map(|&w| u64::from(w)).sum());
+            assert!(e.weighted().eq(out.iter().copied()));
+        }
+        let mut out = [0xff; 3];
+        assert_eq!(None, crate::decimal::weighted(b"4111", &mut out));
+        assert_eq!(None, crate::decimal::weighted(b"4x1", &mut out));
+        assert_eq!([0xff; 3], out);
+    }
+
+    #[test]
+    fn test_random_valid_with() {
+        let mut counter = 0u8;
+        let rng = || {
+            counter = counter.wrapping_add(37);
+            counter
+        };
+        let mut buf = [0; 19];
+        assert!(crate::decimal::random_valid_with(rng, &mut buf));
+        assert!(crate::decimal::valid(&buf));
+
+        // bytes above 249 are skipped
+        let mut bytes = [255, 250, 7, 255, 3].into_iter();
+        let mut buf = [0; 3];
+        assert!(crate::decimal::random_valid_with(
+            || bytes.next().unwrap(),
+            &mut buf
+        ));
+        assert_eq!(b"737", &buf);
+
+        let mut buf = [b'x'];
+        assert!(!crate::decimal::random_valid_with(|| 0, &mut buf));
+        assert_eq!(b"x", &buf);
+    }
+
+    #[test]
+    fn test_mask() {
+        let mut out = [0; 32];
+        for sample in DECIMAL_LUHN_SAMPLES.iter().map(|s| s.as_bytes()) {
+            let len = crate::decimal::mask(sample, &mut out, 4).unwrap();
+            let (hidden, shown) = out[..len].split_at(len - 4);
+            assert!(hidden.iter().all(|&c| c == b'*'));
+            assert_eq!(&sample[len - 4..], shown);
+        }
+        assert_eq!(Some(3), crate::decimal::mask(b"411", &mut out, 4));
+        assert_eq!(b"411", &out[..3]);
+        assert_eq!(Some(3), crate::decimal::mask(b"411", &mut out, 0));
+        assert_eq!(b"***", &out[..3]);
+        assert_eq!(None, crate::decimal::mask(b"4111 1111", &mut out, 4));
+        assert_eq!(None, crate::decimal::mask(b"4111", &mut out[..3], 4));
+    }
+
+    #[test]
+    fn test_check_vec_reports_first_bad_byte() {
+        for sample in DECIMAL_LUHN_SAMPLES.iter().map(|s| s.as_bytes()) {
+            let long = sample.repeat(3);
+            for s in [sample, &long[..]] {
+                assert_eq!(Ok(crate::decimal::valid(s)), crate::decimal::check_vec(s));
+                for pos in 0..s.len() {
+                    let mut bad = s.to_vec();
+                    bad[pos] = b'x';
+                    assert_eq!(Err(pos), crate::decimal::check_vec(&bad));
+                    *bad.last_mut().unwrap() = b'/';
+                    assert_eq!(Err(pos), crate::decimal::check_vec(&bad));
+                }
+            }
+        }
+        assert_eq!(Ok(false), crate::decimal::check_vec(b""));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_validate_lines() {
+        use std::io::Cursor;
+        let mut dump = String::new();
+        for sample in DECIMAL_LUHN_SAMPLES {
+            dump.push_str(sample);
+            dump.push_str("\n\r\n");
+            dump.push_str(&sample[1..]);
+            dump.push('\n');
+        }
+        let invalid = DECIMAL_LUHN_SAMPLES
+            .iter()
+            .filter(|s| !crate::decimal::valid(&s.as_bytes()[1..]))
+            .count();
+        let total = DECIMAL_LUHN_SAMPLES.len() * 2;
+        let counts = crate::decimal::validate_lines(Cursor::new(&dump)).unwrap();
+        assert_eq!((total - invalid, invalid), counts);
+
+        // last line doesn't need a newline, whitespace is not trimmed
+        let dump = "79927398713\n 79927398713\n79927398713";
+        assert_eq!(
+            (2, 1),
+            crate::decimal::validate_lines(Cursor::new(dump)).unwrap()
+        );
+        assert_eq!(
+            (0, 0),
+            crate::decimal::validate_lines(Cursor::new("")).unwrap()
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_alphanum_valid_reader() {
+        use std::io::{Cursor, Read};
+
+        /// Reader returning a single byte per call
+        struct OneByte<'a>(&'a [u8]);
+        impl Read for OneByte<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                match (self.0.split_first(), buf.first_mut()) {
+                    (Some((&c, rest)), Some(out)) => {
+                        *out = c;
+                        self.0 = rest;
+                        Ok(1)
+                    }
+                    _ => Ok(0),
+                }
+            }
+        }
+
+        for sample in DECIMAL_LUHN_SAMPLES.iter().chain(ALPHANUM_LUHN_SAMPLES) {
+            let mut s = sample.as_bytes().to_vec();
+            assert!(crate::alphanum::valid_reader(Cursor::new(&s)).unwrap());
+            // parity must carry over between reads
+            assert!(crate::alphanum::valid_reader(OneByte(&s)).unwrap());
+            s[0] = change_digit(s[0]);
+            assert_eq!(
+                crate::alphanum::valid(&s),
+                crate::alphanum::valid_reader(Cursor::new(&s)).unwrap()
+            );
+            assert_eq!(
+                crate::alphanum::valid(&s),
+                crate::alphanum::valid_reader(OneByte(&s)).unwrap()
+            );
+        }
+        assert!(!crate::alphanum::valid_reader(Cursor::new(b"")).unwrap());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_alphanum_complete() {
+        for sample in DECIMAL_LUHN_SAMPLES.iter().chain(ALPHANUM_LUHN_SAMPLES) {
+            let body = &sample.as_bytes()[..sample.len() - 1];
+            assert_eq!(Some(*sample), crate::alphanum::complete(body).as_deref());
+        }
+        assert_eq!(Some("0"), crate::alphanum::complete(b"").as_deref());
+    }
+
+    #[test]
+    fn test_valid_framed() {
+        for sample in DECIMAL_LUHN_SAMPLES.iter().map(|s| s.as_bytes()) {
+            let mut frame = vec![sample.len() as u8 - 1];
+            frame.extend_from_slice(sample);
+            assert!(crate::decimal::valid_framed(&frame));
+            frame[0] += 1;
+            assert!(!crate::decimal::valid_framed(&frame));
+            frame[0] -= 2;
+            assert!(!crate::decimal::valid_framed(&frame));
+        }
+        assert!(!crate::decimal::valid_framed(b""));
+        assert!(!crate::decimal::valid_framed(b"\x00"));
+        assert!(crate::decimal::valid_framed(b"\x000"));
+        assert!(!crate::decimal::valid_framed(b"\xff0"));
+    }
+
+    #[test]
+    fn test_rolling_luhn_matches_scratch() {
+        use std::collections::VecDeque;
+        let mut r = crate::RollingLuhn::<8>::default();
+        let mut model = VecDeque::new();
+        let mut seed = 0x853c49e6748fea9bu64;
+        for _ in 0..10_000 {
+            let seed = xorshift(&mut seed);
+            let digit = (seed >> 32) as u8 % 10;
+            match seed % 4 {
+                0 => assert_eq!(model.len() < 8, r.push_back(digit)),
+                1 => assert_eq!(model.len() < 8, r.push_front(digit)),
+                2 => assert_eq!(model.pop_back(), r.pop_back()),
+                _ => assert_eq!(model.pop_front(), r.pop_front()),
+            }
+            if model.len() < 8 {
+                match seed % 4 {
+                    0 => model.push_back(digit),
+                    1 => model.push_front(digit),
+                    _ => {}
+                }
+            }
+            let ascii = model.iter().map(|d| d + b'0').collect::<Vec<_>>();
+            assert_eq!(model.len(), r.len());
+            assert_eq!(
+                !ascii.is_empty() && crate::decimal::valid(&ascii),
+                r.valid()
+            );
+            assert_eq!(crate::decimal::checksum(&ascii), Some(r.checksum()));
+        }
+    }
+
+    #[test]
+    fn test_valid_variant() {
+        let mut seed = 0x2545f4914f6cdd1d_u64;
+        for len in 0..40 {
+            let mut number = Vec::new();
+            for _ in 0..len {
+                let seed = xorshift(&mut seed);
+                number.push(b'0' + (seed % 10) as u8);
+            }
+            assert_eq!(
+                !number.is_empty() && crate::decimal::valid(&number),
+                crate::decimal::valid_variant(&number, false)
+            );
+            // doubling from the right most digit is the same as appending a zero
+            let mut padded = number.clone();
+            padded.push(b'0');
+            assert_eq!(
+                !number.is_empty() && crate::decimal::valid(&padded),
+                crate::decimal::valid_variant(&number, true)
+            );
         }
+        assert!(!crate::decimal::valid_variant(b"", true));
+        assert!(!crate::decimal::valid_variant(b"8x", true));
     }
 
-    const ALPHANUM_LUHN_SAMPLES: &'static [&str] = &[
+    #[test]
+    fn test_mixer_state_round_trip() {
+        for sample in DECIMAL_LUHN_SAMPLES {
+            let digits = sample.as_bytes();
+            for split in 0..=digits.len() {
+                let mut m = crate::Mixer::default();
+                for c in &digits[..split] {
+                    m.push(c - b'0');
+                }
+                let state = m.to_state();
+                let mut restored = crate::Mixer::from_state(state);
+                assert_eq!(state, restored.to_state());
+                assert_eq!(m.valid(), restored.valid());
+                assert_eq!(m.checksum(), restored.checksum());
+                assert_eq!(m.len(), restored.len());
+                for c in &digits[split..] {
+                    restored.push(c - b'0');
+                }
+                assert!(restored.valid());
+            }
+        }
+    }
+
+    #[test]
+    fn test_decimal_and_alphanum_agree_on_digits() {
+        for sample in DECIMAL_LUHN_SAMPLES {
+            let sample = sample.as_bytes();
+            for i in 0..sample.len() {
+                let mut s = Vec::from(sample);
+                s[i] = change_digit(s[i]);
+                assert_eq!(crate::decimal::valid(&s), crate::alphanum::valid(&s));
+                let body = &sample[..i];
+                assert_eq!(
+                    crate::decimal::checksum(body),
+                    crate::alphanum::checksum(body)
+                );
+            }
+        }
+    }
+
+    const ALPHANUM_LUHN_SAMPLES: &[&str] = &[
         "US5949181045", // Microsoft
         "US38259P5089", // Google
         "US0378331005", // Apple
@@ -440,6 +4046,45 @@ mod test {
         "KR4301Q93579",
     ];
 
+    #[test]
+    fn test_checksum_auto() {
+        for sample in DECIMAL_LUHN_SAMPLES {
+            let body = &sample.as_bytes()[..sample.len() - 1];
+            let check = crate::decimal::checksum(body);
+            debug_assert_eq!(check, crate::alphanum::checksum(body));
+            assert_eq!(check, crate::checksum_auto(body));
+        }
+        for sample in ALPHANUM_LUHN_SAMPLES {
+            let body = &sample.as_bytes()[..sample.len() - 1];
+            assert_eq!(crate::alphanum::checksum(body), crate::checksum_auto(body));
+            assert_eq!(
+                sample.as_bytes().last().copied(),
+                crate::checksum_auto(body)
+            );
+        }
+    }
+
+    #[test]
+    fn test_alphanum_vec_matches_scalar() {
+        for sample in ALPHANUM_LUHN_SAMPLES.iter().chain(DECIMAL_LUHN_SAMPLES) {
+            let sample = sample.as_bytes();
+            assert!(crate::alphanum::valid_vec(sample));
+            for i in 0..sample.len() {
+                let mut s = Vec::from(sample);
+                s[i] = change_digit(s[i]);
+                assert_eq!(crate::alphanum::valid(&s), crate::alphanum::valid_vec(&s));
+                for bad in [b'/', b':', b'@', b'[', b'a', 0, 0xff] {
+                    s[i] = bad;
+                    assert!(!crate::alphanum::valid_vec(&s));
+                }
+            }
+        }
+        for len in 0..=40 {
+            let s = (0..len).map(|i| b'0' + i % 43).collect::<Vec<_>>();
+            assert_eq!(crate::alphanum::valid(&s), crate::alphanum::valid_vec(&s));
+        }
+    }
+
     #[test]
     fn test_alphanum_luhn_samples() {
         for sample in ALPHANUM_LUHN_SAMPLES {
@@ -455,6 +4100,17 @@ mod test {
             let (checksum, body) = sample.as_bytes().split_last().unwrap();
             assert_eq!(Some(*checksum), crate::alphanum::checksum(body));
 
+            // streaming version gives the same results
+            let mut m = crate::Mixer::default();
+            for c in sample.as_bytes() {
+                if c.is_ascii_digit() {
+                    m.push(c - b'0');
+                } else {
+                    m.push_letter(*c);
+                }
+            }
+            assert!(m.valid());
+
             // and finally only alphanum numbers are accepted
             let mut s = Vec::from(*sample);
             s[3] = b'x';
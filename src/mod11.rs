@@ -0,0 +1,106 @@
+//! # Mod 11 check digits
+//!
+//! Digits are multiplied by their position counting from the right starting with 1 for the
+//! check digit, the sum must be divisible by 11. This is the scheme used by ISBN-10 among
+//! others. Check digit value of 10 is written as `X` by some schemes, others never assign
+//! numbers that would need it.
+//!
+//! ```
+//! use luhn3::mod11;
+//! // ISBN-10
+//! assert!(mod11::valid(b"0306406152", false));
+//! assert!(mod11::valid(b"080442957X", true));
+//! ```
+
+/// Weighted sum of ASCII digits, the right most one gets weight `weight`
+fn fold(mut weight: u64, ascii: &[u8]) -> Option<u64> {
+    let mut sum = 0;
+    for c in ascii.iter().rev() {
+        let digit = c.wrapping_sub(b'0');
+        if digit > 9 {
+            return None;
+        }
+        sum += u64::from(digit) * weight;
+        weight += 1;
+    }
+    Some(sum % 11)
+}
+
+/// Validate a mod 11 check digit
+///
+/// When `x_allowed` is set the check digit can be `X` standing for 10, the rest must be
+/// decimal digits. Empty input doesn't contain a check digit and is never valid.
+///
+/// ```
+/// use luhn3::mod11::valid;
+///
+/// assert!(valid(b"0306406152", false));
+/// assert!(!valid(b"0306406153", false));
+///
+/// // X is accepted only when allowed
+/// assert!(valid(b"080442957X", true));
+/// assert!(!valid(b"080442957X", false));
+/// ```
+pub fn valid(number: &[u8], x_allowed: bool) -> bool {
+    let Some((&check, body)) = number.split_last() else {
+        return false;
+    };
+    let check = match check {
+        b'0'..=b'9' => u64::from(check - b'0'),
+        b'X' if x_allowed => 10,
+        _ => return false,
+    };
+    match fold(2, body) {
+        Some(sum) => (sum + check).is_multiple_of(11),
+        None => false,
+    }
+}
+
+/// Compute a mod 11 check digit as an ASCII byte
+///
+/// Returns `b'X'` if check digit value is 10 and `x_allowed` is set, `None` if it isn't
+/// or the input is not decimal.
+///
+/// ```
+/// use luhn3::mod11::checksum;
+///
+/// assert_eq!(Some(b'2'), checksum(b"030640615", false));
+/// assert_eq!(Some(b'X'), checksum(b"080442957", true));
+///
+/// // this number can't get a check digit without X
+/// assert_eq!(None, checksum(b"080442957", false));
+/// ```
+pub fn checksum(body: &[u8], x_allowed: bool) -> Option<u8> {
+    match (11 - fold(2, body)?) % 11 {
+        10 if x_allowed => Some(b'X'),
+        10 => None,
+        check => Some(b'0' + check as u8),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn test_mod11_isbn10() {
+        for isbn in [
+            &b"0306406152"[..],
+            b"080442957X",
+            b"0198526636",
+            b"0262033844",
+        ] {
+            let (_, body) = isbn.split_last().unwrap();
+            assert!(super::valid(isbn, true));
+            assert_eq!(isbn.last().copied(), super::checksum(body, true));
+            // adjacent transposition is always detected
+            for i in 0..body.len() - 1 {
+                let mut s = isbn.to_vec();
+                s.swap(i, i + 1);
+                assert_eq!(s == isbn, super::valid(&s, true));
+            }
+        }
+        assert!(!super::valid(b"", true));
+        assert!(!super::valid(b"6X", false));
+        assert!(super::valid(b"6X", true));
+        assert!(!super::valid(b"08044295X7", true));
+    }
+}
@@ -0,0 +1,110 @@
+//! # Luhn mod N algorithm
+//!
+//! Generalization of Luhn algorithm to an arbitrary base: every second digit starting from
+//! the right one of the body is doubled and replaced with a sum of its digits in base `N`.
+//! With base 10 results match those of [`decimal`](crate::decimal).
+//!
+//! ```
+//! use luhn3::modn;
+//! assert!(modn::valid_slice(10, &[4, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1]));
+//! ```
+
+/// Sum of transformed digits modulo `base`, `None` if any digit is out of range
+fn fold(base: u32, mut double: bool, digits: &[u32]) -> Option<u64> {
+    let base = u64::from(base);
+    let mut sum = 0;
+    for &digit in digits.iter().rev() {
+        let digit = u64::from(digit);
+        if digit >= base {
+            return None;
+        }
+        let addend = if double { digit * 2 } else { digit };
+        sum = (sum + addend / base + addend % base) % base;
+        double = !double;
+    }
+    Some(sum)
+}
+
+/// Validate a check digit using Luhn mod N algorithm
+///
+/// Digits are given as values in `0..base` range with the check digit last. Returns
+/// `false` if any digit is out of range, base is less than 2 or input is empty.
+///
+/// ```
+/// use luhn3::modn::valid_slice;
+///
+/// // base 16
+/// assert!(valid_slice(16, &[0xa, 0xb, 0xc, 0xd, 0xe, 0xf, 0xb]));
+/// assert!(!valid_slice(16, &[0xa, 0xb, 0xc, 0xd, 0xe, 0xf, 0xc]));
+///
+/// // digit out of range
+/// assert!(!valid_slice(3, &[0, 3]));
+/// ```
+pub fn valid_slice(base: u32, digits: &[u32]) -> bool {
+    base >= 2 && !digits.is_empty() && fold(base, false, digits) == Some(0)
+}
+
+/// Compute a check digit using Luhn mod N algorithm
+///
+/// Digits are given as values in `0..base` range, result is in the same range.
+/// Returns `None` if any digit is out of range or base is less than 2.
+///
+/// ```
+/// use luhn3::modn::checksum_slice;
+///
+/// assert_eq!(Some(0xb), checksum_slice(16, &[0xa, 0xb, 0xc, 0xd, 0xe, 0xf]));
+/// ```
+pub fn checksum_slice(base: u32, digits: &[u32]) -> Option<u32> {
+    if base < 2 {
+        return None;
+    }
+    let sum = fold(base, true, digits)?;
+    Some(((u64::from(base) - sum) % u64::from(base)) as u32)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{checksum_slice, valid_slice};
+
+    #[test]
+    fn test_base_10_matches_decimal() {
+        for sample in ["378282246310005", "4012888888881881", "4222222222222"] {
+            let digits = sample
+                .bytes()
+                .map(|c| u32::from(c - b'0'))
+                .collect::<Vec<_>>();
+            assert!(valid_slice(10, &digits));
+            let (check, body) = digits.split_last().unwrap();
+            assert_eq!(Some(*check), checksum_slice(10, body));
+            let mut digits = digits.clone();
+            for i in 0..digits.len() {
+                digits[i] = (digits[i] + 1) % 10;
+                let bytes = digits.iter().map(|d| b'0' + *d as u8).collect::<Vec<_>>();
+                assert_eq!(crate::decimal::valid(&bytes), valid_slice(10, &digits));
+            }
+        }
+    }
+
+    #[test]
+    fn test_wide_digits() {
+        let base = 1_000_003;
+        let mut digits = vec![999_999, 17, 1_000_002, 0, u32::from(u16::MAX)];
+        let check = checksum_slice(base, &digits).unwrap();
+        assert!(check < base);
+        digits.push(check);
+        assert!(valid_slice(base, &digits));
+        digits[1] += 1;
+        assert!(!valid_slice(base, &digits));
+        assert!(!valid_slice(base, &[base]));
+        assert!(!valid_slice(1, &[0]));
+        assert!(!valid_slice(10, &[]));
+        assert_eq!(None, checksum_slice(0, &[]));
+        assert!(valid_slice(
+            u32::MAX,
+            &[
+                u32::MAX - 1,
+                checksum_slice(u32::MAX, &[u32::MAX - 1]).unwrap()
+            ]
+        ));
+    }
+}
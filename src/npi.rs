@@ -0,0 +1,71 @@
+//! # National Provider Identifier
+//!
+//! US healthcare NPI is a 10 digit number with a Luhn check digit at the end. Check digit
+//! is calculated as if the number was prefixed with `80840`, so plain [`decimal::valid`]
+//! gives a wrong answer.
+//!
+//! ```
+//! use luhn3::npi;
+//! assert!(npi::valid(b"1234567893"));
+//! assert!(!luhn3::decimal::valid(b"1234567893"));
+//! ```
+use crate::decimal;
+
+/// Prefix used for NPI numbers, `80` for health applications and `840` for the United States
+const PREFIX: &[u8; 5] = b"80840";
+
+/// Validate a 10 digit NPI number
+///
+/// ```
+/// use luhn3::npi::valid;
+///
+/// assert!(valid(b"1234567893"));
+///
+/// // wrong check digit
+/// assert!(!valid(b"1234567890"));
+///
+/// // NPI must be exactly 10 digits long
+/// assert!(!valid(b"808401234567893"));
+/// ```
+pub fn valid(npi: &[u8]) -> bool {
+    if npi.len() != 10 {
+        return false;
+    }
+    let mut buf = [0; 15];
+    buf[..5].copy_from_slice(PREFIX);
+    buf[5..].copy_from_slice(npi);
+    decimal::valid_arr(&buf)
+}
+
+/// Compute a check digit for the first 9 digits of NPI number
+///
+/// ```
+/// use luhn3::npi::check_digit;
+///
+/// assert_eq!(Some(b'3'), check_digit(b"123456789"));
+///
+/// // body must be exactly 9 digits long
+/// assert_eq!(None, check_digit(b"12345678"));
+/// ```
+pub fn check_digit(body: &[u8]) -> Option<u8> {
+    if body.len() != 9 {
+        return None;
+    }
+    let mut buf = [0; 14];
+    buf[..5].copy_from_slice(PREFIX);
+    buf[5..].copy_from_slice(body);
+    decimal::checksum(&buf)
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn test_canonical_npi() {
+        let npi = b"1234567893";
+        assert!(super::valid(npi));
+        let (check, body) = npi.split_last().unwrap();
+        assert_eq!(Some(*check), super::check_digit(body));
+        assert!(crate::decimal::valid(b"808401234567893"));
+        assert!(!super::valid(b"123456789x"));
+    }
+}
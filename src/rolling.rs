@@ -0,0 +1,149 @@
+use crate::tables::DOUBLE_LUT;
+
+/// Digit mixer that allows adding and removing digits at both ends
+///
+/// Unlike [`Mixer`](crate::Mixer) digits are kept in a ring buffer of `N` digits so they
+/// can be removed later, which is what a text field validating a number as it's edited
+/// needs. Sums of plain and doubled digits are kept separately for even and odd positions
+/// so [`RollingLuhn::valid`] and [`RollingLuhn::checksum`] take constant time after any
+/// edit.
+///
+/// # Examples
+/// ```rust
+///    use luhn3::RollingLuhn;
+///    let mut r = RollingLuhn::<19>::default();
+///    for d in [0, 1, 2, 8, 8, 8, 8, 8, 8, 8, 8, 1, 8, 8, 1] {
+///        r.push_back(d);
+///    }
+///    assert!(!r.valid());
+///    r.push_front(4);
+///    assert!(r.valid());
+///    assert_eq!(Some(1), r.pop_back());
+///    assert_eq!(b'1', r.checksum());
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollingLuhn<const N: usize> {
+    digits: [u8; N],
+    /// Index of the first digit in `digits`
+    head: usize,
+    len: usize,
+    /// Position of the first digit, decreases with every push to the front. Only parity
+    /// matters so it's allowed to wrap
+    front: usize,
+    /// Sums of digits as is at even and odd positions
+    plain: [u64; 2],
+    /// Sums of doubled digits at even and odd positions
+    double: [u64; 2],
+}
+
+impl<const N: usize> Default for RollingLuhn<N> {
+    fn default() -> Self {
+        Self {
+            digits: [0; N],
+            head: 0,
+            len: 0,
+            front: 0,
+            plain: [0; 2],
+            double: [0; 2],
+        }
+    }
+}
+
+impl<const N: usize> RollingLuhn<N> {
+    /// Number of digits currently kept
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if there are no digits
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn add(&mut self, pos: usize, digit: u8) {
+        self.plain[pos & 1] += u64::from(digit);
+        self.double[pos & 1] += u64::from(DOUBLE_LUT[digit as usize]);
+    }
+
+    fn remove(&mut self, pos: usize, digit: u8) {
+        self.plain[pos & 1] -= u64::from(digit);
+        self.double[pos & 1] -= u64::from(DOUBLE_LUT[digit as usize]);
+    }
+
+    /// Position of the last digit
+    fn back(&self) -> usize {
+        self.front.wrapping_add(self.len).wrapping_sub(1)
+    }
+
+    /// Add a digit to the right, returns `false` if there's no space left
+    ///
+    /// # Panics
+    /// Function contains [debug_assert] to ensure digit is in `0..=9` range
+    pub fn push_back(&mut self, digit: u8) -> bool {
+        debug_assert!(digit < 10);
+        if self.len == N {
+            return false;
+        }
+        self.digits[(self.head + self.len) % N] = digit;
+        self.len += 1;
+        self.add(self.back(), digit);
+        true
+    }
+
+    /// Add a digit to the left, returns `false` if there's no space left
+    ///
+    /// # Panics
+    /// Function contains [debug_assert] to ensure digit is in `0..=9` range
+    pub fn push_front(&mut self, digit: u8) -> bool {
+        debug_assert!(digit < 10);
+        if self.len == N {
+            return false;
+        }
+        self.head = (self.head + N - 1) % N;
+        self.digits[self.head] = digit;
+        self.len += 1;
+        self.front = self.front.wrapping_sub(1);
+        self.add(self.front, digit);
+        true
+    }
+
+    /// Remove the right most digit
+    pub fn pop_back(&mut self) -> Option<u8> {
+        if self.len == 0 {
+            return None;
+        }
+        let digit = self.digits[(self.head + self.len - 1) % N];
+        self.remove(self.back(), digit);
+        self.len -= 1;
+        Some(digit)
+    }
+
+    /// Remove the left most digit
+    pub fn pop_front(&mut self) -> Option<u8> {
+        if self.len == 0 {
+            return None;
+        }
+        let digit = self.digits[self.head];
+        self.remove(self.front, digit);
+        self.head = (self.head + 1) % N;
+        self.front = self.front.wrapping_add(1);
+        self.len -= 1;
+        Some(digit)
+    }
+
+    /// Check if the right most digit is a valid check digit
+    ///
+    /// Same as [`Mixer::valid`](crate::Mixer::valid) no digits means no check digit so an
+    /// empty [`RollingLuhn`] is never valid
+    pub fn valid(&self) -> bool {
+        let p = self.back() & 1;
+        !self.is_empty() && (self.plain[p] + self.double[p ^ 1]).is_multiple_of(10)
+    }
+
+    /// Compute a check digit for current digits as an ASCII byte
+    pub fn checksum(&self) -> u8 {
+        let p = self.back() & 1;
+        let sum = self.double[p] + self.plain[p ^ 1];
+        b'0' + ((10 - (sum % 10)) % 10) as u8
+    }
+}
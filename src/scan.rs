@@ -0,0 +1,101 @@
+use crate::tables::DOUBLE_LUT;
+
+/// Iterator over offsets of valid fixed length decimal numbers in a text
+///
+/// Created by [`decimal::scan`][crate::decimal::scan] and
+/// [`decimal::scan_vec`][crate::decimal::scan_vec]. Every window of `len` consecutive
+/// decimal digits is checked, including windows inside of a longer run of digits, bytes
+/// around the window are not looked at.
+///
+/// Window sums are maintained incrementally: each digit is added once as it enters the
+/// window and removed once as it leaves so scanning is linear in the size of the text
+/// regardless of `len`. [`scan_vec`][crate::decimal::scan_vec] only uses SSE2 to skip over
+/// text without digits: windows overlap so folding each one with the vectorized fold
+/// reads every digit `len` times instead of twice, on a megabyte of back to back card
+/// numbers that is 15 to 70% slower than updating the sums.
+///
+/// # Examples
+/// ```rust
+///    let text = b"card: 4111111111111111, exp: 12/29";
+///    let found = luhn3::decimal::scan(text, 16).collect::<Vec<_>>();
+///    assert_eq!(found, [6]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Scan<'a> {
+    text: &'a [u8],
+    len: usize,
+    /// Index of the next byte to look at, current window ends right before it
+    pos: usize,
+    /// Number of consecutive digits before `pos`, saturates at `len`
+    run: usize,
+    /// Sums of digits at even and odd positions of the text as is
+    plain: [u64; 2],
+    /// Sums of doubled digits at even and odd positions of the text
+    double: [u64; 2],
+    vectorized: bool,
+}
+
+impl<'a> Scan<'a> {
+    pub(crate) fn new(text: &'a [u8], len: usize, vectorized: bool) -> Self {
+        Self {
+            text,
+            len,
+            pos: 0,
+            run: 0,
+            plain: [0; 2],
+            double: [0; 2],
+            vectorized,
+        }
+    }
+}
+
+impl Iterator for Scan<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        while self.pos < self.text.len() {
+            if self.run == 0 && self.vectorized {
+                match crate::find_digit(&self.text[self.pos..], true) {
+                    Some(offset) => self.pos += offset,
+                    None => {
+                        self.pos = self.text.len();
+                        return None;
+                    }
+                }
+            }
+            let digit = self.text[self.pos].wrapping_sub(b'0');
+            if digit > 9 {
+                self.run = 0;
+                self.plain = [0; 2];
+                self.double = [0; 2];
+                self.pos += 1;
+                continue;
+            }
+
+            let parity = self.pos & 1;
+            self.plain[parity] += u64::from(digit);
+            self.double[parity] += u64::from(DOUBLE_LUT[digit as usize]);
+            if self.run == self.len {
+                let first = self.pos - self.len;
+                let digit = self.text[first] - b'0';
+                self.plain[first & 1] -= u64::from(digit);
+                self.double[first & 1] -= u64::from(DOUBLE_LUT[digit as usize]);
+            } else {
+                self.run += 1;
+            }
+            self.pos += 1;
+
+            // check digit is the last one in the window and stays as is,
+            // digits at the other parity are doubled
+            if self.run == self.len
+                && (self.plain[parity] + self.double[parity ^ 1]).is_multiple_of(10)
+            {
+                return Some(self.pos - self.len);
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,128 @@
+//! # Detection of available vector instructions
+//!
+//! Crate itself only uses SSE2 and SSSE3 and only when they are enabled at compile time,
+//! see `simd` feature. [`best_available`] is for callers building their own dispatch or wanting to
+//! log which instructions are available.
+//!
+//! ```
+//! use luhn3::simd::{best_available, SimdLevel};
+//! if best_available() >= SimdLevel::Sse2 {
+//!     // vectorized code paths can be used
+//! }
+//! ```
+
+/// Vector instruction set extensions, ordered from the least to the most capable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SimdLevel {
+    /// No supported vector instructions or not an x86 target
+    None,
+    /// SSE2, present on all x86_64 CPUs
+    Sse2,
+    /// SSSE3 with byte shuffles
+    Ssse3,
+    /// AVX2 with 256 bit integer operations
+    Avx2,
+    /// AVX-512 foundation instructions
+    Avx512,
+}
+
+/// Best vector instruction set extension available
+///
+/// With `std` feature enabled the CPU is queried at runtime once and the result is cached,
+/// without it the answer is based on target features enabled at compile time. Always
+/// returns [`SimdLevel::None`] on targets other than x86 and x86_64.
+pub fn best_available() -> SimdLevel {
+    #[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+    {
+        use core::sync::atomic::{AtomicU8, Ordering};
+        // 0 means not detected yet, otherwise level + 1
+        static CACHE: AtomicU8 = AtomicU8::new(0);
+        match CACHE.load(Ordering::Relaxed) {
+            0 => {
+                let level = detect();
+                CACHE.store(level as u8 + 1, Ordering::Relaxed);
+                level
+            }
+            cached => from_u8(cached - 1),
+        }
+    }
+    #[cfg(not(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64"))))]
+    {
+        compiled()
+    }
+}
+
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+fn detect() -> SimdLevel {
+    if std::arch::is_x86_feature_detected!("avx512f") {
+        SimdLevel::Avx512
+    } else if std::arch::is_x86_feature_detected!("avx2") {
+        SimdLevel::Avx2
+    } else if std::arch::is_x86_feature_detected!("ssse3") {
+        SimdLevel::Ssse3
+    } else if std::arch::is_x86_feature_detected!("sse2") {
+        SimdLevel::Sse2
+    } else {
+        SimdLevel::None
+    }
+}
+
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+fn from_u8(level: u8) -> SimdLevel {
+    match level {
+        4 => SimdLevel::Avx512,
+        3 => SimdLevel::Avx2,
+        2 => SimdLevel::Ssse3,
+        1 => SimdLevel::Sse2,
+        _ => SimdLevel::None,
+    }
+}
+
+/// Best extension enabled at compile time
+#[cfg_attr(
+    all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")),
+    allow(dead_code)
+)]
+fn compiled() -> SimdLevel {
+    if cfg!(target_feature = "avx512f") {
+        SimdLevel::Avx512
+    } else if cfg!(target_feature = "avx2") {
+        SimdLevel::Avx2
+    } else if cfg!(target_feature = "ssse3") {
+        SimdLevel::Ssse3
+    } else if cfg!(target_feature = "sse2") {
+        SimdLevel::Sse2
+    } else {
+        SimdLevel::None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_best_available() {
+        let level = best_available();
+        assert_eq!(level, best_available());
+        // runtime detection can only find more than what's enabled at compile time
+        assert!(level >= compiled());
+        if cfg!(target_arch = "x86_64") {
+            assert!(level >= SimdLevel::Sse2);
+        } else if cfg!(not(target_arch = "x86")) {
+            assert_eq!(level, SimdLevel::None);
+        }
+    }
+
+    #[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+    #[test]
+    fn test_best_available_cached() {
+        let level = best_available();
+        assert_eq!(level, detect());
+        assert!(level >= compiled());
+        // later calls are served from the cache, including other threads
+        assert_eq!(level, best_available());
+        let other = std::thread::spawn(best_available).join().unwrap();
+        assert_eq!(level, other);
+    }
+}
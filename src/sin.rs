@@ -0,0 +1,53 @@
+//! # Canadian Social Insurance Number
+//!
+//! SIN is a 9 digit number with a Luhn check digit at the end, usually written in three
+//! groups of three digits separated with spaces.
+//!
+//! First digit encodes the province of registration. `0` is not assigned to people and
+//! `8` is used for business numbers, such numbers are still accepted here since they pass
+//! the check digit validation and the well known example SIN starts with `0`.
+//!
+//! ```
+//! use luhn3::sin;
+//! assert!(sin::valid(b"046 454 286"));
+//! assert!(sin::valid(b"046454286"));
+//! ```
+use crate::decimal;
+
+/// Validate a 9 digit SIN, either as is or in three space separated groups
+///
+/// ```
+/// use luhn3::sin::valid;
+///
+/// assert!(valid(b"046 454 286"));
+///
+/// // wrong check digit
+/// assert!(!valid(b"046 454 287"));
+///
+/// // groups must be three digits each
+/// assert!(!valid(b"0464 54 286"));
+/// ```
+pub fn valid(sin: &[u8]) -> bool {
+    let digits = match sin {
+        [a, b, c, b' ', d, e, f, b' ', g, h, i] => [*a, *b, *c, *d, *e, *f, *g, *h, *i],
+        _ => match <[u8; 9]>::try_from(sin) {
+            Ok(digits) => digits,
+            Err(_) => return false,
+        },
+    };
+    decimal::valid_arr(&digits)
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn test_sin_formats() {
+        assert!(super::valid(b"046454286"));
+        assert!(super::valid(b"046 454 286"));
+        assert!(!super::valid(b"046-454-286"));
+        assert!(!super::valid(b"046  454 286"));
+        assert!(!super::valid(b"46454286"));
+        assert!(!super::valid(b"0046454286"));
+        assert!(!super::valid(b"046 454 28 6"));
+    }
+}
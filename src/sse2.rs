@@ -0,0 +1,344 @@
+//! Vectorized folds for x86 and x86_64
+//!
+//! Only SSE2 instructions are required so code runs on any x86_64 CPU and on 32bit x86
+//! CPUs with SSE2 present. When SSSE3 is enabled at compile time, for example with
+//! `-C target-cpu=native`, doubled digits are looked up with a byte shuffle instead.
+//! Selection happens at compile time, there's no runtime detection.
+//!
+//! # Safety
+//!
+//! The module is compiled only when SSE2 is enabled for the target, see `cfg` on its
+//! declaration, so SSE2 intrinsics are always available here. `SAFETY` comments on
+//! individual blocks only explain why their memory accesses stay in bounds.
+#[cfg(target_arch = "x86")]
+use core::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::*;
+
+/// Number of bytes processed in one step
+const LANES: usize = 16;
+
+// chunks are copied into a buffer of LANES bytes and loaded as a single register,
+// changing one without the other would read out of bounds
+const _: () = assert!(LANES == core::mem::size_of::<__m128i>());
+
+/// Lanes to double when checking a check digit, last lane contains the right most digit
+const DOUBLE_EVEN: [u8; LANES] = [
+    0xff, 0, 0xff, 0, 0xff, 0, 0xff, 0, 0xff, 0, 0xff, 0, 0xff, 0, 0xff, 0,
+];
+
+/// Lanes to double when computing a missing check digit
+const DOUBLE_ODD: [u8; LANES] = [
+    0, 0xff, 0, 0xff, 0, 0xff, 0, 0xff, 0, 0xff, 0, 0xff, 0, 0xff, 0, 0xff,
+];
+
+/// Copy a chunk of at most LANES bytes into a buffer aligned to the right, padded with `b'0'`
+///
+/// Avoids a call to `memcpy` for partial chunks
+#[inline(always)]
+fn load_chunk(chunk: &[u8]) -> [u8; LANES] {
+    if let Ok(full) = <[u8; LANES]>::try_from(chunk) {
+        return full;
+    }
+    let mut lo = [b'0'; 8];
+    let mut hi = [b'0'; 8];
+    match chunk.len() {
+        0 => {}
+        1..=8 => crate::copy_from_small_slice(&mut hi, chunk),
+        len => {
+            let (l, h) = chunk.split_at(len - 8);
+            crate::copy_from_small_slice(&mut lo, l);
+            crate::copy_from_small_slice(&mut hi, h);
+        }
+    }
+    let mut buf = [0; LANES];
+    buf[..8].copy_from_slice(&lo);
+    buf[8..].copy_from_slice(&hi);
+    buf
+}
+
+/// Replace digits `0..=9` in lanes selected by `double` with their doubled contributions
+#[cfg(not(target_feature = "ssse3"))]
+#[inline(always)]
+fn double_digits(v: __m128i, double: __m128i) -> __m128i {
+    // SAFETY: register operations only
+    unsafe {
+        let nine = _mm_set1_epi8(9);
+        let d = _mm_add_epi8(v, v);
+        let d = _mm_sub_epi8(d, _mm_and_si128(_mm_cmpgt_epi8(d, nine), nine));
+        _mm_or_si128(_mm_and_si128(double, d), _mm_andnot_si128(double, v))
+    }
+}
+
+/// Replace digits `0..=9` in lanes selected by `double` with their doubled contributions
+///
+/// Same as the SSE2 version but reads [`DOUBLE_LUT`](crate::tables::DOUBLE_LUT) with a
+/// single `pshufb`
+#[cfg(target_feature = "ssse3")]
+#[inline(always)]
+fn double_digits(v: __m128i, double: __m128i) -> __m128i {
+    // SAFETY: register operations only, `pshufb` is available since this version is
+    // compiled only when SSSE3 is enabled for the target
+    unsafe {
+        let lut = _mm_setr_epi8(0, 2, 4, 6, 8, 1, 3, 5, 7, 9, 0, 0, 0, 0, 0, 0);
+        let d = _mm_shuffle_epi8(lut, v);
+        _mm_or_si128(_mm_and_si128(double, d), _mm_andnot_si128(double, v))
+    }
+}
+
+/// Sum of Luhn transformed decimal digits, 16 digits at a time
+///
+/// Input is split into 16 byte chunks starting from the right, the left most chunk is padded
+/// with `b'0'` from the left so positions of doubled digits stay the same for every chunk.
+/// Doubling is performed arithmetically: `paddb` with itself followed by subtracting 9 from
+/// lanes above 9 with a compare and mask, unless SSSE3 allows a table lookup.
+///
+/// `correct` is set when the right most digit should be doubled, same as in `fold36`
+#[inline(always)]
+pub(crate) fn fold10v_sse2(correct: bool, ascii: &[u8]) -> Option<u64> {
+    let double = if correct { &DOUBLE_ODD } else { &DOUBLE_EVEN };
+    // SAFETY: `double` and `buf` are `[u8; LANES]` and loads read LANES bytes from them,
+    // the store writes LANES bytes into `sums` which is two `u64`
+    unsafe {
+        let zero = _mm_setzero_si128();
+        let nine = _mm_set1_epi8(9);
+        let ascii_zero = _mm_set1_epi8(b'0' as i8);
+        let double = _mm_loadu_si128(double.as_ptr().cast());
+        let mut acc = _mm_setzero_si128();
+
+        for chunk in ascii.rchunks(LANES) {
+            let buf = load_chunk(chunk);
+            let v = _mm_sub_epi8(_mm_loadu_si128(buf.as_ptr().cast()), ascii_zero);
+
+            // anything outside of 0..=9 stays above zero after saturating subtraction
+            if _mm_movemask_epi8(_mm_cmpeq_epi8(_mm_subs_epu8(v, nine), zero)) != 0xffff {
+                return None;
+            }
+
+            let t = double_digits(v, double);
+            acc = _mm_add_epi64(acc, _mm_sad_epu8(t, zero));
+        }
+
+        let mut sums = [0u64; 2];
+        _mm_storeu_si128(sums.as_mut_ptr().cast(), acc);
+        Some(sums[0] + sums[1])
+    }
+}
+
+/// Sum of Luhn transformed decimal digits for inputs of at most [`LANES`] bytes
+///
+/// Same as [`fold10v_sse2`] but the whole input goes into a single register so there's no
+/// loop and no accumulator, digits are at most 9 so two `u16` lanes produced by `psadbw`
+/// can't overflow.
+#[inline(always)]
+pub(crate) fn fold10v_short(correct: bool, ascii: &[u8]) -> Option<u64> {
+    debug_assert!(ascii.len() <= LANES);
+    let double = if correct { &DOUBLE_ODD } else { &DOUBLE_EVEN };
+    let buf = load_chunk(ascii);
+    // SAFETY: loads read LANES bytes from `double` and `buf`, both are `[u8; LANES]`
+    unsafe {
+        let zero = _mm_setzero_si128();
+        let nine = _mm_set1_epi8(9);
+        let double = _mm_loadu_si128(double.as_ptr().cast());
+        let v = _mm_sub_epi8(
+            _mm_loadu_si128(buf.as_ptr().cast()),
+            _mm_set1_epi8(b'0' as i8),
+        );
+
+        if _mm_movemask_epi8(_mm_cmpeq_epi8(_mm_subs_epu8(v, nine), zero)) != 0xffff {
+            return None;
+        }
+
+        let t = double_digits(v, double);
+        let sad = _mm_sad_epu8(t, zero);
+        let sum = _mm_add_epi16(sad, _mm_unpackhi_epi64(sad, sad));
+        Some(_mm_cvtsi128_si32(sum) as u16 as u64)
+    }
+}
+
+/// Sum of Luhn transformed decimal digits, 16 digits at a time starting from the left
+///
+/// Total length is known up front so which lanes to double depends only on its parity,
+/// chunks are consumed in order and the last partial chunk is padded with `b'0'` from the
+/// right. Otherwise same as [`fold10v_sse2`].
+#[inline(always)]
+pub(crate) fn fold10v_forward_sse2(correct: bool, ascii: &[u8]) -> Option<u64> {
+    // lane i is at index 16 * k + i, its distance from the right most digit has the same
+    // parity as `len - 1 - i`
+    let double = if !ascii.len().is_multiple_of(2) ^ correct {
+        &DOUBLE_ODD
+    } else {
+        &DOUBLE_EVEN
+    };
+    let chunks = ascii.chunks_exact(LANES);
+    let rest = chunks.remainder();
+    let mut last = [b'0'; LANES];
+    last[..rest.len()].copy_from_slice(rest);
+    // SAFETY: `chunks_exact` yields chunks of LANES bytes and `last` is `[u8; LANES]` so
+    // loads read LANES bytes from a slice of that length, same for `double`, the store
+    // writes LANES bytes into `sums` which is two `u64`
+    unsafe {
+        let zero = _mm_setzero_si128();
+        let nine = _mm_set1_epi8(9);
+        let ascii_zero = _mm_set1_epi8(b'0' as i8);
+        let double = _mm_loadu_si128(double.as_ptr().cast());
+        let mut acc = _mm_setzero_si128();
+
+        for chunk in chunks.chain(core::iter::once(&last[..])) {
+            let v = _mm_sub_epi8(_mm_loadu_si128(chunk.as_ptr().cast()), ascii_zero);
+
+            if _mm_movemask_epi8(_mm_cmpeq_epi8(_mm_subs_epu8(v, nine), zero)) != 0xffff {
+                return None;
+            }
+
+            let t = double_digits(v, double);
+            acc = _mm_add_epi64(acc, _mm_sad_epu8(t, zero));
+        }
+
+        let mut sums = [0u64; 2];
+        _mm_storeu_si128(sums.as_mut_ptr().cast(), acc);
+        Some(sums[0] + sums[1])
+    }
+}
+
+/// Sum of Luhn transformed alphanumeric symbols, 16 bytes at a time
+///
+/// A letter expands into two digits so it doesn't change parity of digits to the left of
+/// it, but each letter flips parity compared to [`fold10v_sse2`] where every byte is one
+/// digit. Flips are computed from a suffix XOR of the letter bit mask and carried over to
+/// the chunk on the left. Letter value `10 * a + b` contributes `a` and `b` with `b` being
+/// the right most digit, both are computed with compares since SSE2 has no byte shuffles.
+///
+/// `correct` is set when the right most digit should be doubled, same as in `fold36`
+#[inline(always)]
+pub(crate) fn fold36_sse2(correct: bool, ascii: &[u8]) -> Option<u64> {
+    let double = if correct { &DOUBLE_ODD } else { &DOUBLE_EVEN };
+    const BITS: [u8; LANES] = [1, 2, 4, 8, 16, 32, 64, 128, 1, 2, 4, 8, 16, 32, 64, 128];
+    // parity flips from letters in chunks to the right
+    let mut carry = 0u32;
+    // SAFETY: loads read LANES bytes from `double`, `BITS` and `buf`, all are `[u8; LANES]`,
+    // the store writes LANES bytes into `sums` which is two `u64`
+    unsafe {
+        let zero = _mm_setzero_si128();
+        let one = _mm_set1_epi8(1);
+        let seven = _mm_set1_epi8(7);
+        let nine = _mm_set1_epi8(9);
+        let ascii_zero = _mm_set1_epi8(b'0' as i8);
+        let ascii_a = _mm_set1_epi8(b'A' as i8);
+        let double = _mm_loadu_si128(double.as_ptr().cast());
+        let bits = _mm_loadu_si128(BITS.as_ptr().cast());
+        let mut acc = _mm_setzero_si128();
+
+        for chunk in ascii.rchunks(LANES) {
+            let buf = load_chunk(chunk);
+            let c = _mm_loadu_si128(buf.as_ptr().cast());
+            let v = _mm_sub_epi8(c, ascii_zero);
+
+            let digit = _mm_cmpeq_epi8(_mm_subs_epu8(v, nine), zero);
+            let letter = _mm_cmpeq_epi8(
+                _mm_subs_epu8(_mm_sub_epi8(c, ascii_a), _mm_set1_epi8(25)),
+                zero,
+            );
+            if _mm_movemask_epi8(_mm_or_si128(digit, letter)) != 0xffff {
+                return None;
+            }
+
+            // bit i of flips tells if there's an odd number of letters to the right of lane i
+            let mut flips = _mm_movemask_epi8(letter) as u32;
+            flips ^= flips >> 1;
+            flips ^= flips >> 2;
+            flips ^= flips >> 4;
+            flips ^= flips >> 8;
+            let next_carry = carry ^ (flips & 1);
+            let flips = (flips >> 1) ^ (carry * 0xffff);
+            carry = next_carry;
+            let lo = u64::from(flips as u8) * 0x0101010101010101;
+            let hi = u64::from((flips >> 8) as u8) * 0x0101010101010101;
+            let flips = _mm_set_epi64x(hi as i64, lo as i64);
+            let flips = _mm_cmpeq_epi8(_mm_and_si128(flips, bits), bits);
+            let double = _mm_xor_si128(double, flips);
+
+            // letters are 'A' - '0' - 10 = 7 below their value after subtracting '0'
+            let v = _mm_sub_epi8(v, _mm_and_si128(letter, seven));
+            // a is 1 for 10..=19, 2 for 20..=29 and 3 for 30..=35, 0 for digits
+            let a = _mm_sub_epi8(
+                _mm_sub_epi8(
+                    _mm_and_si128(letter, one),
+                    _mm_cmpgt_epi8(v, _mm_set1_epi8(19)),
+                ),
+                _mm_cmpgt_epi8(v, _mm_set1_epi8(29)),
+            );
+            let a2 = _mm_add_epi8(a, a);
+            let a8 = _mm_add_epi8(_mm_add_epi8(a2, a2), _mm_add_epi8(a2, a2));
+            let b = _mm_sub_epi8(v, _mm_add_epi8(a8, a2));
+
+            let d = _mm_add_epi8(b, b);
+            let d = _mm_sub_epi8(d, _mm_and_si128(_mm_cmpgt_epi8(d, nine), nine));
+            let t = _mm_or_si128(
+                _mm_and_si128(double, _mm_add_epi8(d, a)),
+                _mm_andnot_si128(double, _mm_add_epi8(b, a2)),
+            );
+            acc = _mm_add_epi64(acc, _mm_sad_epu8(t, zero));
+        }
+
+        let mut sums = [0u64; 2];
+        _mm_storeu_si128(sums.as_mut_ptr().cast(), acc);
+        Some(sums[0] + sums[1])
+    }
+}
+
+/// Check if all the bytes are decimal digits or capital ASCII letters, 16 bytes at a time
+#[inline(always)]
+pub(crate) fn all_alphanum_sse2(ascii: &[u8]) -> bool {
+    // SAFETY: the only load reads LANES bytes from `buf` which is `[u8; LANES]`
+    unsafe {
+        let zero = _mm_setzero_si128();
+        let ascii_zero = _mm_set1_epi8(b'0' as i8);
+        let ascii_a = _mm_set1_epi8(b'A' as i8);
+        let nine = _mm_set1_epi8(9);
+        let twenty_five = _mm_set1_epi8(25);
+
+        for chunk in ascii.chunks(LANES) {
+            let buf = load_chunk(chunk);
+            let v = _mm_loadu_si128(buf.as_ptr().cast());
+
+            // both are zero only for bytes in the corresponding range
+            let digit = _mm_subs_epu8(_mm_sub_epi8(v, ascii_zero), nine);
+            let letter = _mm_subs_epu8(_mm_sub_epi8(v, ascii_a), twenty_five);
+            let valid = _mm_cmpeq_epi8(_mm_min_epu8(digit, letter), zero);
+            if _mm_movemask_epi8(valid) != 0xffff {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Index of the first byte that is a decimal digit if `digit` is set or isn't otherwise,
+/// 16 bytes at a time
+///
+/// Unlike other functions here the last partial chunk is checked one byte at a time since
+/// padding consists of digits
+#[inline(always)]
+pub(crate) fn find_digit_sse2(ascii: &[u8], digit: bool) -> Option<usize> {
+    let chunks = ascii.chunks_exact(LANES);
+    let rest = chunks.remainder();
+    let invert = if digit { 0 } else { 0xffff };
+    // SAFETY: `chunks_exact` yields chunks of exactly LANES bytes, loads read that many
+    unsafe {
+        let zero = _mm_setzero_si128();
+        let ascii_zero = _mm_set1_epi8(b'0' as i8);
+        let nine = _mm_set1_epi8(9);
+
+        for (ix, chunk) in chunks.enumerate() {
+            let v = _mm_loadu_si128(chunk.as_ptr().cast());
+            let digits = _mm_cmpeq_epi8(_mm_subs_epu8(_mm_sub_epi8(v, ascii_zero), nine), zero);
+            let mask = _mm_movemask_epi8(digits) ^ invert;
+            if mask != 0 {
+                return Some(ix * LANES + mask.trailing_zeros() as usize);
+            }
+        }
+    }
+    let offset = ascii.len() - rest.len();
+    Some(offset + rest.iter().position(|c| c.is_ascii_digit() == digit)?)
+}
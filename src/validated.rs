@@ -0,0 +1,203 @@
+use crate::{alphanum, decimal, LuhnError};
+use core::ops::Deref;
+use core::str::FromStr;
+
+/// Decimal number of exactly `N` digits ending with a valid Luhn check digit
+///
+/// The only way to construct it is [`ValidatedDecimal::try_new`] which runs
+/// [`decimal::valid_arr`] so any instance is guaranteed to be valid.
+///
+/// # Examples
+/// ```rust
+///    use luhn3::ValidatedDecimal;
+///    let card = ValidatedDecimal::<16>::try_new(*b"4012888888881881").unwrap();
+///    assert_eq!(b"4012888888881881", card.as_bytes());
+///    assert_eq!(b'4', card[0]);
+///    assert_eq!(card, "4012888888881881");
+/// ```
+///
+/// Length is a part of the type
+/// ```compile_fail
+///    use luhn3::ValidatedDecimal;
+///    let card: ValidatedDecimal<16> = ValidatedDecimal::try_new(*b"4222222222222").unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ValidatedDecimal<const N: usize>([u8; N]);
+
+impl<const N: usize> ValidatedDecimal<N> {
+    /// Validate a decimal number
+    ///
+    /// Returns `None` if input is not decimal or the check digit is invalid
+    /// ```rust
+    ///    use luhn3::ValidatedDecimal;
+    ///    assert!(ValidatedDecimal::try_new(*b"4012888888881881").is_some());
+    ///    assert!(ValidatedDecimal::try_new(*b"4012888888881882").is_none());
+    /// ```
+    pub fn try_new(bytes: [u8; N]) -> Option<Self> {
+        if decimal::valid_arr(&bytes) {
+            Some(Self(bytes))
+        } else {
+            None
+        }
+    }
+
+    /// ASCII digits of the number including the check digit
+    pub fn as_bytes(&self) -> &[u8; N] {
+        &self.0
+    }
+}
+
+impl<const N: usize> Deref for ValidatedDecimal<N> {
+    type Target = [u8; N];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const N: usize> TryFrom<&[u8]> for ValidatedDecimal<N> {
+    type Error = LuhnError;
+
+    /// Validate a decimal number given as a slice
+    ///
+    /// ```rust
+    ///    use luhn3::{LuhnError, ValidatedDecimal};
+    ///    let card = ValidatedDecimal::<16>::try_from(&b"4012888888881881"[..]);
+    ///    assert!(card.is_ok());
+    ///    let card = ValidatedDecimal::<16>::try_from(&b"401288888888188"[..]);
+    ///    assert_eq!(Err(LuhnError::InvalidLength), card);
+    /// ```
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        let bytes = <[u8; N]>::try_from(value).map_err(|_| LuhnError::InvalidLength)?;
+        decimal::check(&bytes)?;
+        Ok(Self(bytes))
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for ValidatedDecimal<N> {
+    type Error = LuhnError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::try_from(value.as_bytes())
+    }
+}
+
+impl<const N: usize> FromStr for ValidatedDecimal<N> {
+    type Err = LuhnError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
+/// Alphanumeric code of exactly `N` symbols ending with a valid Luhn check digit
+///
+/// The only way to construct it is [`ValidatedAlphanum::try_new`] or conversion from
+/// a slice or a string, all of them run [`alphanum::valid_arr`] so any instance is
+/// guaranteed to be valid.
+///
+/// # Examples
+/// ```rust
+///    use luhn3::ValidatedAlphanum;
+///    let isin: ValidatedAlphanum<12> = "US5949181045".parse().unwrap();
+///    assert_eq!(b"US5949181045", isin.as_bytes());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ValidatedAlphanum<const N: usize>([u8; N]);
+
+impl<const N: usize> ValidatedAlphanum<N> {
+    /// Validate an alphanumeric code
+    ///
+    /// Returns `None` if input contains anything other than decimal digits and capital
+    /// letters or the check digit is invalid
+    pub fn try_new(bytes: [u8; N]) -> Option<Self> {
+        if alphanum::valid_arr(&bytes) {
+            Some(Self(bytes))
+        } else {
+            None
+        }
+    }
+
+    /// ASCII symbols of the code including the check digit
+    pub fn as_bytes(&self) -> &[u8; N] {
+        &self.0
+    }
+}
+
+impl<const N: usize> Deref for ValidatedAlphanum<N> {
+    type Target = [u8; N];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const N: usize> TryFrom<&[u8]> for ValidatedAlphanum<N> {
+    type Error = LuhnError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        let bytes = <[u8; N]>::try_from(value).map_err(|_| LuhnError::InvalidLength)?;
+        alphanum::check(&bytes)?;
+        Ok(Self(bytes))
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for ValidatedAlphanum<N> {
+    type Error = LuhnError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::try_from(value.as_bytes())
+    }
+}
+
+impl<const N: usize> FromStr for ValidatedAlphanum<N> {
+    type Err = LuhnError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
+/// Compare validated numbers with strings and byte slices in both directions
+macro_rules! impl_eq {
+    ($ty:ident) => {
+        impl<const N: usize> PartialEq<[u8]> for $ty<N> {
+            fn eq(&self, other: &[u8]) -> bool {
+                self.0[..] == *other
+            }
+        }
+
+        impl<const N: usize> PartialEq<str> for $ty<N> {
+            fn eq(&self, other: &str) -> bool {
+                self.0[..] == *other.as_bytes()
+            }
+        }
+
+        impl<const N: usize> PartialEq<&str> for $ty<N> {
+            fn eq(&self, other: &&str) -> bool {
+                self.0[..] == *other.as_bytes()
+            }
+        }
+
+        impl<const N: usize> PartialEq<$ty<N>> for [u8] {
+            fn eq(&self, other: &$ty<N>) -> bool {
+                other == self
+            }
+        }
+
+        impl<const N: usize> PartialEq<$ty<N>> for str {
+            fn eq(&self, other: &$ty<N>) -> bool {
+                other == self
+            }
+        }
+
+        impl<const N: usize> PartialEq<$ty<N>> for &str {
+            fn eq(&self, other: &$ty<N>) -> bool {
+                other == self
+            }
+        }
+    };
+}
+
+impl_eq!(ValidatedDecimal);
+impl_eq!(ValidatedAlphanum);
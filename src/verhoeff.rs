@@ -0,0 +1,102 @@
+//! # Verhoeff check digits
+//!
+//! Verhoeff algorithm uses the dihedral group D5 instead of addition modulo 10 and
+//! detects all single digit errors and all adjacent transpositions, including the twin
+//! errors Luhn can't see. Used by [Aadhaar][crate::aadhaar] among others.
+//!
+//! ```
+//! use luhn3::verhoeff;
+//! assert!(verhoeff::valid(b"2363"));
+//! assert_eq!(Some(b'3'), verhoeff::checksum(b"236"));
+//! ```
+
+/// Multiplication table of the dihedral group D5
+const MUL: [[u8; 10]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+    [1, 2, 3, 4, 0, 6, 7, 8, 9, 5],
+    [2, 3, 4, 0, 1, 7, 8, 9, 5, 6],
+    [3, 4, 0, 1, 2, 8, 9, 5, 6, 7],
+    [4, 0, 1, 2, 3, 9, 5, 6, 7, 8],
+    [5, 9, 8, 7, 6, 0, 4, 3, 2, 1],
+    [6, 5, 9, 8, 7, 1, 0, 4, 3, 2],
+    [7, 6, 5, 9, 8, 2, 1, 0, 4, 3],
+    [8, 7, 6, 5, 9, 3, 2, 1, 0, 4],
+    [9, 8, 7, 6, 5, 4, 3, 2, 1, 0],
+];
+
+/// Permutation applied to a digit depending on its position from the right, repeats every 8
+const PERM: [[u8; 10]; 8] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+    [1, 5, 7, 6, 2, 8, 3, 0, 9, 4],
+    [5, 8, 0, 3, 7, 9, 6, 1, 4, 2],
+    [8, 9, 1, 6, 0, 4, 3, 5, 2, 7],
+    [9, 4, 5, 3, 1, 2, 8, 7, 6, 0],
+    [4, 2, 8, 6, 5, 7, 3, 9, 0, 1],
+    [2, 7, 9, 3, 8, 0, 6, 4, 1, 5],
+    [7, 0, 4, 6, 9, 1, 3, 2, 5, 8],
+];
+
+/// Inverse elements of D5
+const INV: [u8; 10] = [0, 4, 3, 2, 1, 5, 6, 7, 8, 9];
+
+/// Fold ASCII digits starting from the right most one at the given position
+fn fold(offset: usize, ascii: &[u8]) -> Option<u8> {
+    let mut acc = 0;
+    for (i, c) in ascii.iter().rev().enumerate() {
+        let digit = c.wrapping_sub(b'0');
+        if digit > 9 {
+            return None;
+        }
+        acc = MUL[acc as usize][PERM[(i + offset) % 8][digit as usize] as usize];
+    }
+    Some(acc)
+}
+
+/// Validate a Verhoeff check digit
+///
+/// Empty input doesn't contain a check digit and is never valid.
+///
+/// ```
+/// use luhn3::verhoeff::valid;
+///
+/// assert!(valid(b"2363"));
+///
+/// // adjacent transposition
+/// assert!(!valid(b"3263"));
+///
+/// assert!(!valid(b""));
+/// ```
+pub fn valid(ascii: &[u8]) -> bool {
+    !ascii.is_empty() && fold(0, ascii) == Some(0)
+}
+
+/// Compute a Verhoeff check digit as an ASCII byte
+///
+/// Returns `None` on non decimal input
+///
+/// ```
+/// use luhn3::verhoeff::checksum;
+///
+/// assert_eq!(Some(b'3'), checksum(b"236"));
+/// assert_eq!(None, checksum(b"23x"));
+/// ```
+pub fn checksum(ascii: &[u8]) -> Option<u8> {
+    Some(b'0' + INV[fold(1, ascii)? as usize])
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn test_verhoeff_detects_transpositions() {
+        let number = b"236412341234598";
+        let check = super::checksum(number).unwrap();
+        let mut full = number.to_vec();
+        full.push(check);
+        assert!(super::valid(&full));
+        for i in 0..full.len() - 1 {
+            let mut swapped = full.clone();
+            swapped.swap(i, i + 1);
+            assert_eq!(full[i] == full[i + 1], super::valid(&swapped));
+        }
+    }
+}
@@ -0,0 +1,53 @@
+//! # Visa card numbers
+//!
+//! Visa numbers start with `4` and are 13, 16 or 19 digits long with a Luhn check digit
+//! at the end.
+//!
+//! ```
+//! use luhn3::visa;
+//! assert!(visa::valid(b"4012888888881881"));
+//! // MasterCard
+//! assert!(!visa::valid(b"5555555555554444"));
+//! ```
+use crate::decimal;
+
+/// Validate a Visa card number
+///
+/// ```
+/// use luhn3::visa::valid;
+///
+/// assert!(valid(b"4222222222222"));
+/// assert!(valid(b"4111111111111111"));
+/// assert!(valid(b"4012888888881888883"));
+///
+/// // valid check digit but Visa numbers are never 17 digits long
+/// assert!(!valid(b"41111111111111113"));
+/// ```
+pub fn valid(number: &[u8]) -> bool {
+    matches!(number.len(), 13 | 16 | 19) && number.first() == Some(&b'4') && decimal::valid(number)
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn test_visa_lengths() {
+        for number in [
+            &b"4222222222222"[..],
+            b"4012888888881881",
+            b"4012888888881888883",
+        ] {
+            assert!(super::valid(number));
+            let mut s = number.to_vec();
+            s[0] = b'3';
+            crate::decimal::set_check_digit(&mut s);
+            assert!(crate::decimal::valid(&s));
+            assert!(!super::valid(&s));
+        }
+        for len in 1..=20 {
+            let mut s = vec![b'4'; len];
+            crate::decimal::set_check_digit(&mut s);
+            assert!(crate::decimal::valid(&s));
+            assert_eq!(matches!(len, 13 | 16 | 19), super::valid(&s));
+        }
+    }
+}
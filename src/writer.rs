@@ -0,0 +1,86 @@
+use crate::Mixer;
+use core::fmt;
+
+/// [`fmt::Write`] adapter that validates decimal digits as they are written
+///
+/// Digits are fed into a [`Mixer`], ASCII spaces and dashes are skipped, anything else
+/// marks the whole stream as invalid. Writing never fails.
+///
+/// # Examples
+/// ```rust
+///    use core::fmt::Write;
+///    use luhn3::LuhnWriter;
+///    let mut w = LuhnWriter::default();
+///    write!(w, "{} {}-{}", 4111, "1111", 1111_1111).unwrap();
+///    assert!(w.valid());
+/// ```
+#[derive(Default)]
+pub struct LuhnWriter {
+    mixer: Mixer,
+    invalid: bool,
+}
+
+impl LuhnWriter {
+    /// Check if the last written digit is a valid check digit
+    ///
+    /// Returns `false` if anything other than digits and separators was written
+    pub fn valid(&self) -> bool {
+        !self.invalid && self.mixer.valid()
+    }
+
+    /// Compute a check digit for written digits as an ASCII byte
+    ///
+    /// Returns `None` if anything other than digits and separators was written
+    /// ```rust
+    ///    use core::fmt::Write;
+    ///    use luhn3::LuhnWriter;
+    ///    let mut w = LuhnWriter::default();
+    ///    write!(w, "4012 8888 8888 188").unwrap();
+    ///    assert_eq!(Some(b'1'), w.checksum());
+    ///    write!(w, "?").unwrap();
+    ///    assert_eq!(None, w.checksum());
+    /// ```
+    pub fn checksum(&self) -> Option<u8> {
+        if self.invalid {
+            None
+        } else {
+            Some(self.mixer.checksum())
+        }
+    }
+}
+
+impl fmt::Write for LuhnWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.bytes() {
+            match c {
+                b'0'..=b'9' => self.mixer.push(c - b'0'),
+                b' ' | b'-' => {}
+                _ => self.invalid = true,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::LuhnWriter;
+    use core::fmt::Write;
+
+    #[test]
+    fn test_card_in_pieces() {
+        let mut w = LuhnWriter::default();
+        for piece in ["4012", "8888", "8888", "1881"] {
+            write!(w, "{} ", piece).unwrap();
+        }
+        assert!(w.valid());
+
+        let mut w = LuhnWriter::default();
+        write!(w, "{}{}", 401288888888u64, 1882).unwrap();
+        assert!(!w.valid());
+
+        let mut w = LuhnWriter::default();
+        write!(w, "4012.8888.8888.1881").unwrap();
+        assert!(!w.valid());
+    }
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "capi")]
+use std::ptr::null;
+
+// link the library, functions are only referenced by symbol names
+extern crate luhn3;
+
+// declared the same way a C caller would, resolved by the linker
+extern "C" {
+    fn luhn3_decimal_valid(ptr: *const u8, len: usize) -> bool;
+    fn luhn3_decimal_checksum(ptr: *const u8, len: usize) -> i32;
+}
+
+fn valid(s: &[u8]) -> bool {
+    // SAFETY: pointer and length come from a live slice
+    unsafe { luhn3_decimal_valid(s.as_ptr(), s.len()) }
+}
+
+fn checksum(s: &[u8]) -> i32 {
+    // SAFETY: pointer and length come from a live slice
+    unsafe { luhn3_decimal_checksum(s.as_ptr(), s.len()) }
+}
+
+#[test]
+fn valid_through_ffi() {
+    assert!(valid(b"4012888888881881"));
+    assert!(!valid(b"4012888888881882"));
+    assert!(!valid(b"US5949181045"));
+    assert!(valid(b""));
+    // SAFETY: null pointer is allowed
+    assert!(!unsafe { luhn3_decimal_valid(null(), 16) });
+}
+
+#[test]
+fn checksum_through_ffi() {
+    assert_eq!(i32::from(b'1'), checksum(b"401288888888188"));
+    assert_eq!(i32::from(b'0'), checksum(b""));
+    assert_eq!(-1, checksum(b"US594918104"));
+    // SAFETY: null pointer is allowed
+    assert_eq!(-1, unsafe { luhn3_decimal_checksum(null(), 15) });
+}
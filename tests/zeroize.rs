@@ -0,0 +1,17 @@
+#![cfg(feature = "zeroize")]
+use luhn3::card::SecretCardNumber;
+use std::mem::{size_of, ManuallyDrop};
+
+#[test]
+fn secret_card_number_is_zeroed_on_drop() {
+    let mut card = ManuallyDrop::new(SecretCardNumber::new(b"4012888888881881").unwrap());
+    let ptr: *mut SecretCardNumber = &mut *card;
+    let bytes =
+        || unsafe { std::slice::from_raw_parts(ptr.cast::<u8>(), size_of::<SecretCardNumber>()) };
+    assert!(bytes().windows(16).any(|w| w == b"4012888888881881"));
+
+    // SAFETY: card is dropped only once and not used afterwards, only its memory is read
+    unsafe { std::ptr::drop_in_place(ptr) };
+    assert!(!bytes().windows(16).any(|w| w == b"4012888888881881"));
+    assert!(!bytes().contains(&b'8'));
+}